@@ -296,6 +296,11 @@ pub async fn ping() -> IpcResult<IpcResponse> {
     send_request(&IpcRequest::Ping).await
 }
 
+/// Arm or disarm the kill-switch
+pub async fn set_kill_switch(enabled: bool, watch_port: Option<u16>) -> IpcResult<IpcResponse> {
+    send_request(&IpcRequest::SetKillSwitch { enabled, watch_port }).await
+}
+
 /// Check if service is available
 pub async fn is_service_available() -> bool {
     ping().await.is_ok()
@@ -56,6 +56,14 @@ pub enum IpcRequest {
     /// Ping - for connection testing
     Ping,
     
+    /// Arm or disarm the kill-switch that blocks outbound traffic while the core is down
+    SetKillSwitch {
+        /// Whether the kill-switch is armed
+        enabled: bool,
+        /// Local controller port used to detect a core not started by the service
+        watch_port: Option<u16>,
+    },
+    
     /// Shutdown the service (admin only)
     Shutdown,
 }
@@ -5,18 +5,21 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 use crate::core_manager::CoreManager;
+use crate::kill_switch::KillSwitch;
 use crate::log_collector::LogCollector;
 
 /// Service request handler
 pub struct ServiceHandler {
     core_manager: Arc<CoreManager>,
     log_collector: Arc<LogCollector>,
+    kill_switch: Arc<KillSwitch>,
 }
 
 impl ServiceHandler {
     pub fn new(
         core_manager: Arc<CoreManager>,
         log_collector: Arc<LogCollector>,
+        kill_switch: Arc<KillSwitch>,
     ) -> Self {
         // Set up log forwarding
         let sender = log_collector.create_sender();
@@ -31,6 +34,7 @@ impl ServiceHandler {
         Self {
             core_manager,
             log_collector,
+            kill_switch,
         }
     }
 }
@@ -112,11 +116,26 @@ impl aqiu_service_ipc::RequestHandler for ServiceHandler {
                 IpcResponse::success_with_data("Pong", ResponseData::Pong)
             }
             
+            IpcRequest::SetKillSwitch { enabled, watch_port } => {
+                tracing::info!("Setting kill-switch: enabled={}, watch_port={:?}", enabled, watch_port);
+                self.kill_switch.configure(enabled, watch_port);
+                
+                // Apply immediately instead of waiting for the next reconcile pass
+                match self.kill_switch.reconcile(&self.core_manager) {
+                    Ok(blocking) => IpcResponse::success_with_data(
+                        if blocking { "Kill-switch is blocking traffic" } else { "Kill-switch updated" },
+                        ResponseData::Bool(blocking),
+                    ),
+                    Err(e) => IpcResponse::error(1, e),
+                }
+            }
+            
             IpcRequest::Shutdown => {
                 tracing::info!("Shutdown requested");
                 
                 // Stop core first
                 self.core_manager.stop().await;
+                self.kill_switch.release();
                 
                 // Schedule shutdown
                 tokio::spawn(async {
@@ -0,0 +1,494 @@
+//! Kill Switch - Blocks outbound traffic while the core is down
+//!
+//! When armed, the service installs firewall rules that drop all outbound
+//! traffic except loopback whenever the core is not running, so apps never
+//! fall back to a direct connection. Rules are removed as soon as the core
+//! is back or the kill-switch is disarmed.
+//! - Linux: nftables (falls back to iptables)
+//! - macOS: pf anchor
+
+use parking_lot::RwLock;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::core_manager::CoreManager;
+
+/// Interval between reconcile passes
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(2);
+
+#[cfg(target_os = "linux")]
+const NFT_TABLE: &str = "aqiu_killswitch";
+#[cfg(target_os = "linux")]
+const IPTABLES_CHAIN: &str = "AQIU_KILLSWITCH";
+/// iptables fallback: one chain per IP family, with the family's loopback range
+#[cfg(target_os = "linux")]
+const IPTABLES_FAMILIES: [(&str, &str); 2] =
+    [("iptables", "127.0.0.0/8"), ("ip6tables", "::1/128")];
+#[cfg(target_os = "macos")]
+const PF_ANCHOR: &str = "com.apple/aqiu.killswitch";
+
+/// Runs the firewall commands; a trait so rule handling can be tested without root
+pub trait CommandRunner: Send + Sync {
+    /// Run a command and fail on non-zero exit
+    fn run(&self, program: &str, args: &[&str]) -> Result<(), String>;
+    /// Run a command feeding `input` on stdin
+    fn run_with_stdin(&self, program: &str, args: &[&str], input: &str) -> Result<(), String>;
+}
+
+/// Runs commands on the host
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, program: &str, args: &[&str]) -> Result<(), String> {
+        run(program, args)
+    }
+
+    fn run_with_stdin(&self, program: &str, args: &[&str], input: &str) -> Result<(), String> {
+        run_with_stdin(program, args, input)
+    }
+}
+
+/// Kill Switch - tracks desired state and the currently installed rules
+pub struct KillSwitch {
+    /// Whether the kill-switch is armed
+    enabled: RwLock<bool>,
+    /// Controller port of a core started outside the service
+    watch_port: RwLock<Option<u16>>,
+    /// Whether blocking rules are currently installed
+    blocking: RwLock<bool>,
+    /// pf enable token (macOS), released when rules are removed
+    #[allow(dead_code)]
+    pf_token: RwLock<Option<String>>,
+    runner: Box<dyn CommandRunner>,
+}
+
+impl KillSwitch {
+    pub fn new() -> Self {
+        Self::with_runner(Box::new(SystemRunner))
+    }
+
+    pub fn with_runner(runner: Box<dyn CommandRunner>) -> Self {
+        Self {
+            enabled: RwLock::new(false),
+            watch_port: RwLock::new(None),
+            blocking: RwLock::new(false),
+            pf_token: RwLock::new(None),
+            runner,
+        }
+    }
+
+    /// Update the kill-switch configuration
+    pub fn configure(&self, enabled: bool, watch_port: Option<u16>) {
+        *self.enabled.write() = enabled;
+        *self.watch_port.write() = watch_port;
+    }
+
+    /// Whether blocking rules are currently installed
+    pub fn is_blocking(&self) -> bool {
+        *self.blocking.read()
+    }
+
+    /// Check whether the core is alive, either as our child or on the watched port
+    fn core_alive(&self, core_manager: &CoreManager) -> bool {
+        if core_manager.is_running() {
+            return true;
+        }
+
+        match *self.watch_port.read() {
+            Some(port) => {
+                let addr = std::net::SocketAddr::from(([127, 0, 0, 1], port));
+                std::net::TcpStream::connect_timeout(&addr, Duration::from_millis(300)).is_ok()
+            }
+            None => false,
+        }
+    }
+
+    /// Install or remove rules so they match the desired state
+    pub fn reconcile(&self, core_manager: &CoreManager) -> Result<bool, String> {
+        let core_alive = self.core_alive(core_manager);
+        self.apply(core_alive)
+    }
+
+    /// Block only while armed and the core is down
+    fn apply(&self, core_alive: bool) -> Result<bool, String> {
+        let should_block = *self.enabled.read() && !core_alive;
+
+        if should_block && !self.is_blocking() {
+            tracing::warn!("Kill-switch: core is down, blocking outbound traffic");
+            self.install_rules()?;
+            *self.blocking.write() = true;
+        } else if !should_block && self.is_blocking() {
+            tracing::info!("Kill-switch: releasing outbound traffic block");
+            self.remove_rules()?;
+            *self.blocking.write() = false;
+        }
+
+        Ok(self.is_blocking())
+    }
+
+    /// Remove any installed rules (used on shutdown)
+    pub fn release(&self) {
+        if self.is_blocking() {
+            if let Err(e) = self.remove_rules() {
+                tracing::error!("Kill-switch: failed to remove rules: {}", e);
+            }
+            *self.blocking.write() = false;
+        }
+    }
+
+    /// Remove rules left behind by a previous daemon instance (e.g. restarted by
+    /// launchd/systemd while blocking), whose state we no longer have in memory
+    fn clear_stale_rules(&self) {
+        #[cfg(target_os = "linux")]
+        {
+            let _ = self
+                .runner
+                .run("nft", &["delete", "table", "inet", NFT_TABLE]);
+            for (program, _) in IPTABLES_FAMILIES {
+                if let Err(e) = self.remove_iptables_chain(program) {
+                    tracing::warn!(
+                        "Kill-switch: failed to clear stale {} rules: {}",
+                        program,
+                        e
+                    );
+                }
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            let _ = self.runner.run("pfctl", &["-a", PF_ANCHOR, "-F", "rules"]);
+        }
+
+        *self.blocking.write() = false;
+    }
+
+    /// Periodically reconcile rules so a crashed core is caught without app involvement
+    pub async fn run(self: Arc<Self>, core_manager: Arc<CoreManager>) {
+        self.clear_stale_rules();
+
+        loop {
+            if let Err(e) = self.reconcile(&core_manager) {
+                tracing::error!("Kill-switch reconcile failed: {}", e);
+            }
+            tokio::time::sleep(RECONCILE_INTERVAL).await;
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn install_rules(&self) -> Result<(), String> {
+        let ruleset = format!(
+            "table inet {table} {{\n\
+             \tchain output {{\n\
+             \t\ttype filter hook output priority 0; policy drop;\n\
+             \t\toifname \"lo\" accept\n\
+             \t\tip daddr 127.0.0.0/8 accept\n\
+             \t\tip6 daddr ::1 accept\n\
+             \t}}\n\
+             }}\n",
+            table = NFT_TABLE
+        );
+
+        match self.runner.run_with_stdin("nft", &["-f", "-"], &ruleset) {
+            Ok(()) => Ok(()),
+            Err(nft_err) => {
+                tracing::warn!("nft unavailable ({}), falling back to iptables", nft_err);
+                for (program, loopback) in IPTABLES_FAMILIES {
+                    self.install_iptables_chain(program, loopback)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Drop everything but loopback for one IP family. The chain may survive from an
+    /// earlier run, so it is reused and refilled from empty.
+    #[cfg(target_os = "linux")]
+    fn install_iptables_chain(&self, program: &str, loopback: &str) -> Result<(), String> {
+        if self.runner.run(program, &["-N", IPTABLES_CHAIN]).is_err() {
+            self.runner.run(program, &["-F", IPTABLES_CHAIN])?;
+        }
+        self.runner
+            .run(program, &["-A", IPTABLES_CHAIN, "-o", "lo", "-j", "ACCEPT"])?;
+        self.runner.run(
+            program,
+            &["-A", IPTABLES_CHAIN, "-d", loopback, "-j", "ACCEPT"],
+        )?;
+        self.runner
+            .run(program, &["-A", IPTABLES_CHAIN, "-j", "DROP"])?;
+        if self
+            .runner
+            .run(program, &["-C", "OUTPUT", "-j", IPTABLES_CHAIN])
+            .is_err()
+        {
+            self.runner
+                .run(program, &["-I", "OUTPUT", "1", "-j", IPTABLES_CHAIN])?;
+        }
+        Ok(())
+    }
+
+    /// Remove one family's chain and every jump to it; a missing chain is not an error
+    #[cfg(target_os = "linux")]
+    fn remove_iptables_chain(&self, program: &str) -> Result<(), String> {
+        while self
+            .runner
+            .run(program, &["-D", "OUTPUT", "-j", IPTABLES_CHAIN])
+            .is_ok()
+        {}
+        if self
+            .runner
+            .run(program, &["-n", "-L", IPTABLES_CHAIN])
+            .is_err()
+        {
+            return Ok(());
+        }
+        self.runner.run(program, &["-F", IPTABLES_CHAIN])?;
+        self.runner.run(program, &["-X", IPTABLES_CHAIN])
+    }
+
+    #[cfg(target_os = "linux")]
+    fn remove_rules(&self) -> Result<(), String> {
+        if self
+            .runner
+            .run("nft", &["delete", "table", "inet", NFT_TABLE])
+            .is_ok()
+        {
+            return Ok(());
+        }
+
+        let mut result = Ok(());
+        for (program, _) in IPTABLES_FAMILIES {
+            if let Err(e) = self.remove_iptables_chain(program) {
+                result = Err(e);
+            }
+        }
+        result
+    }
+
+    #[cfg(target_os = "macos")]
+    fn install_rules(&self) -> Result<(), String> {
+        let rules = "pass out quick on lo0 all\nblock drop out quick all\n";
+        self.runner
+            .run_with_stdin("pfctl", &["-a", PF_ANCHOR, "-f", "-"], rules)?;
+
+        // `pfctl -E` enables pf and returns a reference token on stderr
+        let output = Command::new("pfctl")
+            .arg("-E")
+            .output()
+            .map_err(|e| format!("Failed to run pfctl: {}", e))?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        *self.pf_token.write() = stderr
+            .lines()
+            .find_map(|line| line.strip_prefix("Token : "))
+            .map(|token| token.trim().to_string());
+
+        Ok(())
+    }
+
+    #[cfg(target_os = "macos")]
+    fn remove_rules(&self) -> Result<(), String> {
+        self.runner
+            .run("pfctl", &["-a", PF_ANCHOR, "-F", "rules"])?;
+
+        if let Some(token) = self.pf_token.write().take() {
+            let _ = self.runner.run("pfctl", &["-X", &token]);
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    fn install_rules(&self) -> Result<(), String> {
+        Err("Kill-switch is not supported on this platform".to_string())
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    fn remove_rules(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Run a command and fail on non-zero exit
+fn run(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} {} failed: {}",
+            program,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Run a command feeding `input` on stdin
+fn run_with_stdin(program: &str, args: &[&str], input: &str) -> Result<(), String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to wait for {}: {}", program, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Records every command; commands whose line starts with one of `failing` fail
+    #[derive(Default)]
+    struct FakeRunner {
+        calls: Arc<Mutex<Vec<String>>>,
+        failing: Vec<String>,
+    }
+
+    impl FakeRunner {
+        fn failing(prefixes: &[&str]) -> Self {
+            Self {
+                failing: prefixes.iter().map(|p| p.to_string()).collect(),
+                ..Self::default()
+            }
+        }
+
+        fn record(&self, line: String) -> Result<(), String> {
+            let failed = self.failing.iter().any(|p| line.starts_with(p.as_str()));
+            self.calls.lock().unwrap().push(line.clone());
+            if failed {
+                Err(format!("{} failed", line))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl CommandRunner for FakeRunner {
+        fn run(&self, program: &str, args: &[&str]) -> Result<(), String> {
+            self.record(format!("{} {}", program, args.join(" ")))
+        }
+
+        fn run_with_stdin(&self, program: &str, args: &[&str], _input: &str) -> Result<(), String> {
+            self.record(format!("{} {}", program, args.join(" ")))
+        }
+    }
+
+    fn kill_switch(runner: FakeRunner) -> (KillSwitch, Arc<Mutex<Vec<String>>>) {
+        let calls = runner.calls.clone();
+        (KillSwitch::with_runner(Box::new(runner)), calls)
+    }
+
+    #[test]
+    fn disarmed_never_blocks() {
+        let (ks, calls) = kill_switch(FakeRunner::default());
+        assert_eq!(ks.apply(false), Ok(false));
+        assert_eq!(ks.apply(true), Ok(false));
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn armed_blocks_only_while_core_is_down() {
+        let (ks, _) = kill_switch(FakeRunner::default());
+        ks.configure(true, Some(9090));
+
+        assert_eq!(ks.apply(true), Ok(false));
+        if cfg!(target_os = "linux") {
+            assert_eq!(ks.apply(false), Ok(true));
+            assert_eq!(ks.apply(true), Ok(false));
+        }
+    }
+
+    #[test]
+    fn disarming_releases_the_block() {
+        let (ks, _) = kill_switch(FakeRunner::default());
+        ks.configure(true, None);
+        if cfg!(target_os = "linux") {
+            assert_eq!(ks.apply(false), Ok(true));
+            ks.configure(false, None);
+            assert_eq!(ks.apply(false), Ok(false));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn iptables_fallback_covers_both_families() {
+        let (ks, calls) = kill_switch(FakeRunner::failing(&["nft", "iptables -C", "ip6tables -C"]));
+        ks.install_rules().unwrap();
+
+        let calls = calls.lock().unwrap();
+        for (program, loopback) in [("iptables", "127.0.0.0/8"), ("ip6tables", "::1/128")] {
+            let expected = [
+                format!("{} -N AQIU_KILLSWITCH", program),
+                format!("{} -A AQIU_KILLSWITCH -o lo -j ACCEPT", program),
+                format!("{} -A AQIU_KILLSWITCH -d {} -j ACCEPT", program, loopback),
+                format!("{} -A AQIU_KILLSWITCH -j DROP", program),
+                format!("{} -I OUTPUT 1 -j AQIU_KILLSWITCH", program),
+            ];
+            for line in &expected {
+                assert!(calls.contains(line), "missing `{}` in {:?}", line, calls);
+            }
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn iptables_install_reuses_existing_chain_and_jump() {
+        let (ks, calls) = kill_switch(FakeRunner::failing(&["nft", "iptables -N", "ip6tables -N"]));
+        ks.install_rules().unwrap();
+
+        let calls = calls.lock().unwrap();
+        assert!(calls.contains(&"iptables -F AQIU_KILLSWITCH".to_string()));
+        assert!(calls.contains(&"ip6tables -F AQIU_KILLSWITCH".to_string()));
+        assert!(!calls.iter().any(|c| c.contains("-I OUTPUT")));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn iptables_removal_is_idempotent() {
+        // No jump left and no chain: nothing to flush, still a success
+        let (ks, calls) = kill_switch(FakeRunner::failing(&[
+            "nft",
+            "iptables -D",
+            "ip6tables -D",
+            "iptables -n -L",
+            "ip6tables -n -L",
+        ]));
+        assert_eq!(ks.remove_rules(), Ok(()));
+        assert!(!calls.lock().unwrap().iter().any(|c| c.contains("-X")));
+
+        // Chain present: it is flushed and deleted for both families
+        let (ks, calls) = kill_switch(FakeRunner::failing(&["nft", "iptables -D", "ip6tables -D"]));
+        assert_eq!(ks.remove_rules(), Ok(()));
+        let calls = calls.lock().unwrap();
+        assert!(calls.contains(&"iptables -X AQIU_KILLSWITCH".to_string()));
+        assert!(calls.contains(&"ip6tables -X AQIU_KILLSWITCH".to_string()));
+    }
+}
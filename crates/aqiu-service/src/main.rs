@@ -7,6 +7,7 @@
 mod core_manager;
 mod log_collector;
 mod handler;
+mod kill_switch;
 
 use aqiu_service_ipc::IpcServer;
 use std::sync::Arc;
@@ -25,10 +26,15 @@ async fn main() -> anyhow::Result<()> {
     // Create log collector
     let log_collector = Arc::new(log_collector::LogCollector::new(1000));
     
+    // Create kill-switch and keep its rules in sync with the core state
+    let kill_switch = Arc::new(kill_switch::KillSwitch::new());
+    tokio::spawn(kill_switch.clone().run(core_manager.clone()));
+    
     // Create request handler
     let handler = Arc::new(handler::ServiceHandler::new(
         core_manager.clone(),
         log_collector.clone(),
+        kill_switch.clone(),
     ));
     
     // Start IPC server
@@ -36,12 +42,15 @@ async fn main() -> anyhow::Result<()> {
     
     // Setup signal handlers for graceful shutdown
     let core_manager_clone = core_manager.clone();
+    let kill_switch_clone = kill_switch.clone();
     tokio::spawn(async move {
         if let Err(e) = wait_for_shutdown().await {
             tracing::error!("Signal handler error: {}", e);
         }
         tracing::info!("Shutdown signal received, stopping core...");
         core_manager_clone.stop().await;
+        // Don't leave the network blocked once the service is gone
+        kill_switch_clone.release();
         std::process::exit(0);
     });
     
@@ -0,0 +1,237 @@
+// ========== Config Backups ==========
+
+const RUNTIME_CONFIG_FILE: &str = "config.runtime.yaml";
+/// Kept per kind, so frequent runtime rewrites can't push out profile or template backups
+const MAX_CONFIG_BACKUPS: usize = 20;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigBackup {
+    pub name: String,
+    /// Which config was backed up: "runtime", "service", "template" or "profile-<id>"
+    pub kind: String,
+    pub created_at: Option<String>,
+    pub size: u64,
+}
+
+fn get_backups_dir() -> PathBuf {
+    get_config_dir().join("backups")
+}
+
+/// Backup file names sorted oldest first (names start with a sortable timestamp)
+fn list_backup_names(dir: &std::path::Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| name.ends_with(".yaml"))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+fn backup_kind_from_name(name: &str) -> Option<&str> {
+    name.strip_suffix(".yaml")?.split_once('_').map(|(_, kind)| kind)
+}
+
+/// Copy the current content of `path` into the backups dir before it is overwritten
+/// with `new_content`. Skipped when the file doesn't exist yet or wouldn't change.
+/// Best effort: a failed backup is logged but never blocks the write.
+pub fn snapshot_config(path: &std::path::Path, kind: &str, new_content: &str) {
+    let Ok(previous) = std::fs::read_to_string(path) else {
+        return;
+    };
+    if previous == new_content {
+        return;
+    }
+
+    let dir = get_backups_dir();
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        tracing::error!("Failed to create backups dir {:?}: {}", dir, e);
+        return;
+    }
+
+    let name = format!("{}_{}.yaml", chrono::Local::now().format("%Y%m%d-%H%M%S-%3f"), kind);
+    if let Err(e) = std::fs::write(dir.join(&name), previous) {
+        tracing::error!("Failed to back up {:?}: {}", path, e);
+        return;
+    }
+    tracing::info!("Backed up {:?} to {}", path, name);
+
+    let names: Vec<String> = list_backup_names(&dir)
+        .into_iter()
+        .filter(|name| backup_kind_from_name(name) == Some(kind))
+        .collect();
+    if names.len() > MAX_CONFIG_BACKUPS {
+        for old in &names[..names.len() - MAX_CONFIG_BACKUPS] {
+            let _ = std::fs::remove_file(dir.join(old));
+        }
+    }
+}
+
+/// Layer `source` over the base template, merge the user overrides into it and
+/// write the result to `target`.
+/// This is the shared write path for every generated config; the previous `target`
+/// is backed up first. If `source` can't be parsed or merged, it is written unchanged.
+fn merge_config_with_overrides(
+    source: &std::path::Path,
+    target: &std::path::Path,
+    kind: &str,
+) -> Result<String, String> {
+    let content = std::fs::read_to_string(source)
+        .map_err(|e| format!("Failed to read config {:?}: {}", source, e))?;
+
+    let merged = match serde_yaml::from_str::<serde_yaml::Value>(&content) {
+        Ok(mut yaml) => {
+            apply_base_template(&mut yaml);
+            let overrides = crate::user_overrides::load_overrides();
+            match crate::user_overrides::apply_overrides_to_yaml(&mut yaml, &overrides) {
+                Ok(()) => serde_yaml::to_string(&yaml).unwrap_or_else(|e| {
+                    tracing::error!("Failed to serialize modified config: {}", e);
+                    content.clone()
+                }),
+                Err(e) => {
+                    tracing::warn!("Warning: Failed to apply user overrides: {}", e);
+                    content.clone()
+                }
+            }
+        }
+        Err(e) => {
+            tracing::error!("Failed to parse config YAML: {}", e);
+            content.clone()
+        }
+    };
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config dir {:?}: {}", parent, e))?;
+    }
+
+    snapshot_config(target, kind, &merged);
+    std::fs::write(target, &merged)
+        .map_err(|e| format!("Failed to write config {:?}: {}", target, e))?;
+
+    Ok(merged)
+}
+
+/// Where a backup of the given kind is restored to
+fn backup_target_path(kind: &str) -> Result<PathBuf, String> {
+    match kind {
+        "runtime" => Ok(get_config_dir().join(RUNTIME_CONFIG_FILE)),
+        "template" => Ok(get_base_template_path()),
+        #[cfg(target_os = "macos")]
+        "service" => Ok(PathBuf::from(SYSTEM_CONFIG_PATH)),
+        _ => match kind.strip_prefix("profile-") {
+            Some(id) => crate::profiles::get_profile_path(id).map(PathBuf::from),
+            None => Err(format!("Cannot restore backup of kind: {}", kind)),
+        },
+    }
+}
+
+/// Ask the running core to load `path` via PUT /configs
+async fn reload_core_config_from_path(state: &MihomoState, path: &std::path::Path) -> Result<(), String> {
+    let (api_host, api_port, api_secret) = {
+        let host = state.api_host.lock().map_err(|e| e.to_string())?.clone();
+        let port = *state.api_port.lock().map_err(|e| e.to_string())?;
+        let secret = get_api_secret_from_state(state);
+        (host, port, secret)
+    };
+
+    let url = format!("http://{}:{}/configs?force=true", api_host, api_port);
+    let client = reqwest::Client::new();
+    let request = add_auth_header(
+        client
+            .put(&url)
+            .json(&serde_json::json!({ "path": path.to_string_lossy() }))
+            .timeout(std::time::Duration::from_secs(10)),
+        api_secret.as_deref()
+    );
+    let response = request.send().await
+        .map_err(|e| format!("Failed to reload config: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to reload config: {}", response.status()));
+    }
+
+    Ok(())
+}
+
+/// List config backups, newest first
+#[tauri::command]
+pub fn list_config_backups() -> Result<Vec<ConfigBackup>, String> {
+    let dir = get_backups_dir();
+    let mut backups: Vec<ConfigBackup> = list_backup_names(&dir)
+        .into_iter()
+        .filter_map(|name| {
+            let kind = backup_kind_from_name(&name)?.to_string();
+            let metadata = std::fs::metadata(dir.join(&name)).ok()?;
+            let created_at = metadata.modified().ok().map(|m| {
+                let time: chrono::DateTime<chrono::Local> = m.into();
+                time.format("%Y-%m-%d %H:%M:%S").to_string()
+            });
+            Some(ConfigBackup {
+                name,
+                kind,
+                created_at,
+                size: metadata.len(),
+            })
+        })
+        .collect();
+    backups.reverse();
+    Ok(backups)
+}
+
+/// Restore a config backup to where it was taken from and apply it to the running core.
+/// Restored profiles and templates are re-merged with the overrides; a restored runtime/service config
+/// is loaded as-is until the next start regenerates it.
+#[tauri::command]
+pub async fn restore_config_backup(
+    app: tauri::AppHandle,
+    state: State<'_, MihomoState>,
+    name: String,
+) -> Result<(), String> {
+    if name.contains('/') || name.contains('\\') || name.contains("..") {
+        return Err("Invalid backup name".to_string());
+    }
+    let kind = backup_kind_from_name(&name)
+        .ok_or("Invalid backup name")?
+        .to_string();
+
+    let backup_path = get_backups_dir().join(&name);
+    let content = std::fs::read_to_string(&backup_path)
+        .map_err(|e| format!("Failed to read backup {}: {}", name, e))?;
+    serde_yaml::from_str::<serde_yaml::Value>(&content)
+        .map_err(|e| format!("Backup {} is not valid YAML: {}", name, e))?;
+
+    let target = backup_target_path(&kind)?;
+    // Keep the current version too, so a restore can itself be undone
+    snapshot_config(&target, &kind, &content);
+    std::fs::write(&target, &content)
+        .map_err(|e| format!("Failed to restore {:?}: {}", target, e))?;
+    tracing::info!("Restored backup {} to {:?}", name, target);
+
+    if !is_core_running(state.inner()) {
+        return Ok(());
+    }
+
+    if let Some(id) = kind.strip_prefix("profile-") {
+        let is_active = crate::profiles::get_active_profile()
+            .ok()
+            .flatten()
+            .map(|p| p.id == id)
+            .unwrap_or(false);
+        if is_active {
+            restart_core_with_overrides(&app, state.clone()).await?;
+        }
+        return Ok(());
+    }
+
+    // The template isn't a config on its own; regenerate the runtime config from it
+    if kind == "template" {
+        return restart_core_with_overrides(&app, state.clone()).await;
+    }
+
+    reload_core_config_from_path(state.inner(), &target).await
+}
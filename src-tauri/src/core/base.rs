@@ -26,6 +26,8 @@ impl Default for CoreMode {
 pub struct MihomoState {
     pub process: Mutex<Option<Child>>,
     pub config_path: Mutex<Option<PathBuf>>,
+    /// Profile the running config was generated from (before overrides)
+    pub profile_path: Mutex<Option<PathBuf>>,
     pub api_host: Mutex<String>,
     pub api_port: Mutex<u16>,
     #[cfg(target_os = "macos")]
@@ -49,6 +51,7 @@ impl Default for MihomoState {
         Self {
             process: Mutex::new(None),
             config_path: Mutex::new(None),
+            profile_path: Mutex::new(None),
             api_host: Mutex::new("127.0.0.1".to_string()),
             api_port: Mutex::new(29090),
             #[cfg(target_os = "macos")]
@@ -133,8 +136,14 @@ pub struct StartOptions {
 
 // ========== Commands ==========
 
-/// Get the path to the Mihomo binary
+/// Get the path to the Mihomo binary: the core picked with `select_core` if it
+/// still exists, otherwise the default single binary
 fn get_mihomo_path() -> PathBuf {
+    selected_core_path().unwrap_or_else(get_default_mihomo_path)
+}
+
+/// Path of the default Mihomo binary (where downloads and imports go)
+fn get_default_mihomo_path() -> PathBuf {
     // Check common locations
     let app_data = dirs::data_local_dir().unwrap_or_default();
     let mihomo_dir = app_data.join("aqiu").join("mihomo");
@@ -204,7 +213,7 @@ fn read_tun_from_config(config_path: &PathBuf) -> Option<bool> {
 }
 
 /// Get logs directory
-fn get_logs_dir() -> PathBuf {
+pub fn get_logs_dir() -> PathBuf {
     let app_data = dirs::data_local_dir().unwrap_or_default();
     app_data.join("aqiu").join("logs")
 }
@@ -305,7 +314,7 @@ fn is_core_running(state: &MihomoState) -> bool {
     // If we explicitly marked the core as stopped, trust it (prevents UI flickering during cleanup)
     if let Ok(stopped) = state.manually_stopped.lock() {
         if *stopped {
-            println!("[is_core_running] manually_stopped=true, returning false");
+            tracing::debug!("[is_core_running] manually_stopped=true, returning false");
             return false;
         }
     }
@@ -317,10 +326,10 @@ fn is_core_running(state: &MihomoState) -> bool {
             if matches!(*mode, CoreMode::Service) {
                 // For Service Mode, check if LaunchDaemon is loaded
                 if is_privileged_helper_loaded() {
-                    println!("[is_core_running] Service Mode LaunchDaemon is loaded");
+                    tracing::debug!("[is_core_running] Service Mode LaunchDaemon is loaded");
                     return true;
                 } else {
-                    println!("[is_core_running] Service Mode is set but LaunchDaemon not loaded");
+                    tracing::debug!("[is_core_running] Service Mode is set but LaunchDaemon not loaded");
                 }
             }
         }
@@ -331,16 +340,16 @@ fn is_core_running(state: &MihomoState) -> bool {
         if let Some(child) = process_lock.as_mut() {
             match child.try_wait() {
                 Ok(None) => {
-                    println!("[is_core_running] Child process is still running");
+                    tracing::debug!("[is_core_running] Child process is still running");
                     return true;
                 }
                 Ok(Some(status)) => {
-                    println!("[is_core_running] Child process exited with status: {}", status);
+                    tracing::debug!("[is_core_running] Child process exited with status: {}", status);
                     // Clear the stale child
                     *process_lock = None;
                 }
                 Err(e) => {
-                    println!("[is_core_running] Error checking child process: {}", e);
+                    tracing::debug!("[is_core_running] Error checking child process: {}", e);
                 }
             }
         }
@@ -350,10 +359,10 @@ fn is_core_running(state: &MihomoState) -> bool {
     if let Ok(pid_lock) = state.root_pid.lock() {
         if let Some(pid) = *pid_lock {
             if is_pid_running(pid) {
-                println!("[is_core_running] Root PID {} is running", pid);
+                tracing::debug!("[is_core_running] Root PID {} is running", pid);
                 return true;
             } else {
-                println!("[is_core_running] Root PID {} is not running anymore", pid);
+                tracing::debug!("[is_core_running] Root PID {} is not running anymore", pid);
             }
         }
     }
@@ -369,7 +378,7 @@ fn is_core_running(state: &MihomoState) -> bool {
                 #[cfg(target_os = "macos")]
                 {
                     if let Some(pid) = find_mihomo_pid_by_port(port) {
-                        println!(
+                        tracing::debug!(
                             "[is_core_running] Port {} is LISTENing by PID {}, recovering state",
                             port, pid
                         );
@@ -400,15 +409,15 @@ fn is_core_running(state: &MihomoState) -> bool {
             #[cfg(target_os = "windows")]
             {
                 // On Windows, is_port_in_use is usually reliable enough
-                println!("[is_core_running] Port {} is in use (Windows)", port);
+                tracing::debug!("[is_core_running] Port {} is in use (Windows)", port);
                 return true;
             }
         } else {
-            println!("[is_core_running] Port {} is not in use", port);
+            tracing::debug!("[is_core_running] Port {} is not in use", port);
         }
     }
     
-    println!("[is_core_running] All checks failed, returning false");
+    tracing::debug!("[is_core_running] All checks failed, returning false");
     false
 }
 
@@ -418,7 +427,7 @@ fn is_core_running(state: &MihomoState) -> bool {
     // If we explicitly marked the core as stopped, trust it
     if let Ok(stopped) = state.manually_stopped.lock() {
         if *stopped {
-            println!("[is_core_running] manually_stopped=true, returning false");
+            tracing::debug!("[is_core_running] manually_stopped=true, returning false");
             return false;
         }
     }
@@ -428,15 +437,15 @@ fn is_core_running(state: &MihomoState) -> bool {
         if let Some(child) = process_lock.as_mut() {
             match child.try_wait() {
                 Ok(None) => {
-                    println!("[is_core_running] Child process is still running");
+                    tracing::debug!("[is_core_running] Child process is still running");
                     return true;
                 }
                 Ok(Some(status)) => {
-                    println!("[is_core_running] Child process exited with status: {}", status);
+                    tracing::debug!("[is_core_running] Child process exited with status: {}", status);
                     *process_lock = None;
                 }
                 Err(e) => {
-                    println!("[is_core_running] Error checking child process: {}", e);
+                    tracing::debug!("[is_core_running] Error checking child process: {}", e);
                 }
             }
         }
@@ -446,14 +455,14 @@ fn is_core_running(state: &MihomoState) -> bool {
     if let Ok(port_lock) = state.api_port.lock() {
         let port = *port_lock;
         if is_port_in_use(port) {
-            println!("[is_core_running] Port {} is in use", port);
+            tracing::debug!("[is_core_running] Port {} is in use", port);
             return true;
         } else {
-            println!("[is_core_running] Port {} is not in use", port);
+            tracing::debug!("[is_core_running] Port {} is not in use", port);
         }
     }
     
-    println!("[is_core_running] All checks failed, returning false");
+    tracing::debug!("[is_core_running] All checks failed, returning false");
     false
 }
 
@@ -544,6 +553,132 @@ fn add_auth_header(builder: reqwest::RequestBuilder, secret: Option<&str>) -> re
     }
 }
 
+/// Controller ports tried when the configured one doesn't answer
+const CANDIDATE_CONTROLLER_PORTS: [u16; 3] = [29090, 9090, 9097];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ControllerEndpoint {
+    pub api_host: String,
+    pub api_port: u16,
+    pub version: Option<String>,
+    /// Whether the endpoint differs from what the state pointed at before
+    pub changed: bool,
+    /// Whether it is the `external-controller` declared in our config
+    pub declared: bool,
+}
+
+/// Probe `/version` (no auth needed) and return the reported version if it's a Mihomo controller
+async fn probe_controller(host: &str, port: u16) -> Option<String> {
+    let client = reqwest::Client::builder()
+        .connect_timeout(std::time::Duration::from_millis(300))
+        .timeout(std::time::Duration::from_millis(800))
+        .build()
+        .ok()?;
+    let response = client
+        .get(format!("http://{}:{}/version", host, port))
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let body: serde_json::Value = response.json().await.ok()?;
+    body.get("version").and_then(|v| v.as_str()).map(|v| v.to_string())
+}
+
+/// Check that a controller is ours: it must reject anonymous requests and accept our secret.
+/// Without a secret there is no way to tell our core from another Mihomo on that port.
+async fn controller_owned(host: &str, port: u16, secret: Option<&str>) -> bool {
+    let Some(secret) = secret.filter(|s| !s.is_empty()) else {
+        return false;
+    };
+    let Ok(client) = reqwest::Client::builder()
+        .connect_timeout(std::time::Duration::from_millis(300))
+        .timeout(std::time::Duration::from_millis(800))
+        .build()
+    else {
+        return false;
+    };
+    let url = format!("http://{}:{}/configs", host, port);
+
+    let anonymous_rejected = matches!(
+        client.get(&url).send().await,
+        Ok(r) if r.status() == reqwest::StatusCode::UNAUTHORIZED
+    );
+    anonymous_rejected
+        && matches!(
+            add_auth_header(client.get(&url), Some(secret)).send().await,
+            Ok(r) if r.status().is_success()
+        )
+}
+
+/// Find the port the core's controller actually listens on and point the state at it.
+/// Tries the config-declared controller first, then the current and well-known ports;
+/// the latter are only taken once they accept our secret.
+async fn rediscover_controller_inner(state: &MihomoState) -> Result<ControllerEndpoint, String> {
+    let (current_host, current_port) = {
+        let host = state.api_host.lock().map_err(|e| e.to_string())?.clone();
+        let port = *state.api_port.lock().map_err(|e| e.to_string())?;
+        (host, port)
+    };
+
+    let declared = parse_external_controller_from_file(&resolve_config_path(state));
+    let secret = get_api_secret_from_state(state);
+
+    let mut candidates: Vec<(String, u16)> = Vec::new();
+    if let Some(declared) = declared.clone() {
+        candidates.push(declared);
+    }
+    candidates.push((current_host.clone(), current_port));
+    for port in CANDIDATE_CONTROLLER_PORTS {
+        candidates.push(("127.0.0.1".to_string(), port));
+    }
+    let mut seen = Vec::new();
+    candidates.retain(|candidate| {
+        let fresh = !seen.contains(candidate);
+        seen.push(candidate.clone());
+        fresh
+    });
+
+    for (host, port) in candidates {
+        let Some(version) = probe_controller(&host, port).await else {
+            continue;
+        };
+        let is_declared = declared.as_ref() == Some(&(host.clone(), port));
+        if !is_declared && !controller_owned(&host, port, secret.as_deref()).await {
+            tracing::debug!("Ignoring controller at {}:{}: not ours", host, port);
+            continue;
+        }
+
+        let changed = host != current_host || port != current_port;
+        if changed {
+            tracing::info!(
+                "Controller moved from {}:{} to {}:{}",
+                current_host, current_port, host, port
+            );
+            *state.api_host.lock().map_err(|e| e.to_string())? = host.clone();
+            *state.api_port.lock().map_err(|e| e.to_string())? = port;
+        }
+
+        return Ok(ControllerEndpoint {
+            api_host: host,
+            api_port: port,
+            version: Some(version),
+            changed,
+            declared: is_declared,
+        });
+    }
+
+    Err("No Mihomo controller found on the configured or known ports".to_string())
+}
+
+/// Re-locate the controller when its port drifted (e.g. Service Mode reloaded a config
+/// with a different `external-controller`) and update the state
+#[tauri::command]
+pub async fn rediscover_controller(state: State<'_, MihomoState>) -> Result<ControllerEndpoint, String> {
+    rediscover_controller_inner(state.inner()).await
+}
+
 async fn verify_survived(state: &MihomoState) -> Result<(), String> {
     // For Service Mode, we must allow more time: LaunchDaemon start + config reload can take seconds.
     // Also, a simple “port in use” check can be racy; prefer verifying the API responds.
@@ -601,25 +736,37 @@ async fn verify_survived(state: &MihomoState) -> Result<(), String> {
         if is_core_running(state) {
             // Stronger check for readiness: API must respond (especially for service mode)
             if api_ready(&api_host, api_port).await {
-                println!(
+                tracing::info!(
                     "Core verified as running and API ready ({} attempt {}/{})",
                     describe, attempt, max_attempts
                 );
                 return Ok(());
             }
-            println!(
+            tracing::info!(
                 "Core seems running but API not ready yet ({} attempt {}/{})",
                 describe, attempt, max_attempts
             );
             continue;
         }
 
-        println!(
+        tracing::info!(
             "Core not yet running, retrying... ({} attempt {}/{})",
             describe, attempt, max_attempts
         );
     }
 
+    // The core may be up on a different port than we expected (config changed across a reload).
+    // Only the port our config declares proves our core came up.
+    if let Ok(endpoint) = rediscover_controller_inner(state).await {
+        if endpoint.changed && endpoint.declared {
+            tracing::info!(
+                "Core verified as running on rediscovered controller {}:{}",
+                endpoint.api_host, endpoint.api_port
+            );
+            return Ok(());
+        }
+    }
+
     Err(format!(
         "Mihomo core did not become ready in time ({}). API not responding at {}:{}.\n\
 Check logs under the app logs directory, and for Service Mode check `/Library/Application Support/aqiu/service.log`.",
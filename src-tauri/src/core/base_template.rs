@@ -0,0 +1,193 @@
+// ========== Base Template ==========
+//
+// A user-maintained YAML base (rules, DNS, groups, ...) layered under every profile
+// before the overrides are applied. The profile wins on conflicts; for `rules` the
+// template can be put first instead via the `base-template-rules-first` override.
+
+const BASE_TEMPLATE_FILE: &str = "base_template.yaml";
+
+/// Lists whose items are identified by `name` and merged item by item
+const NAMED_LIST_KEYS: &[&str] = &["proxies", "proxy-groups"];
+
+fn get_base_template_path() -> PathBuf {
+    get_config_dir().join(BASE_TEMPLATE_FILE)
+}
+
+fn load_base_template() -> Option<serde_yaml::Value> {
+    let content = std::fs::read_to_string(get_base_template_path()).ok()?;
+    match serde_yaml::from_str::<serde_yaml::Value>(&content) {
+        Ok(yaml) if yaml.is_mapping() => Some(yaml),
+        Ok(_) => {
+            tracing::warn!("Base template is not a YAML mapping, ignoring it");
+            None
+        }
+        Err(e) => {
+            tracing::warn!("Failed to parse base template, ignoring it: {}", e);
+            None
+        }
+    }
+}
+
+fn item_name(value: &serde_yaml::Value) -> Option<&str> {
+    value.get("name").and_then(|v| v.as_str())
+}
+
+fn is_match_rule(rule: &serde_yaml::Value) -> bool {
+    rule.as_str()
+        .map(|r| r.trim_start().to_uppercase().starts_with("MATCH,"))
+        .unwrap_or(false)
+}
+
+/// Template rules go either before the profile's or after them; in the latter case
+/// they're kept ahead of the profile's final MATCH so they can still hit
+fn merge_rules(
+    template: Vec<serde_yaml::Value>,
+    profile: Vec<serde_yaml::Value>,
+    rules_first: bool,
+) -> Vec<serde_yaml::Value> {
+    let profile_has_match = profile.iter().any(is_match_rule);
+    let template: Vec<serde_yaml::Value> = template
+        .into_iter()
+        .filter(|rule| !(profile_has_match && is_match_rule(rule)))
+        .collect();
+
+    if rules_first {
+        return template.into_iter().chain(profile).collect();
+    }
+
+    let mut merged = profile;
+    let insert_at = merged
+        .iter()
+        .position(is_match_rule)
+        .unwrap_or(merged.len());
+    merged.splice(insert_at..insert_at, template);
+    merged
+}
+
+/// Profile items win by name; template-only items are appended
+fn merge_named_list(
+    template: Vec<serde_yaml::Value>,
+    profile: Vec<serde_yaml::Value>,
+) -> Vec<serde_yaml::Value> {
+    let mut merged = profile;
+    for item in template {
+        let exists = item_name(&item)
+            .map(|name| merged.iter().any(|p| item_name(p) == Some(name)))
+            .unwrap_or(false);
+        if !exists {
+            merged.push(item);
+        }
+    }
+    merged
+}
+
+/// Deep-merge `template` under `profile`: nested mappings are merged key by key and
+/// the profile's value wins everywhere except the list handling above
+fn merge_template_value(
+    template: serde_yaml::Value,
+    profile: serde_yaml::Value,
+    key: Option<&str>,
+    rules_first: bool,
+) -> serde_yaml::Value {
+    match (template, profile) {
+        (serde_yaml::Value::Mapping(template), serde_yaml::Value::Mapping(mut profile)) => {
+            for (k, template_value) in template {
+                let merged = match profile.remove(&k) {
+                    Some(profile_value) => {
+                        merge_template_value(template_value, profile_value, k.as_str(), rules_first)
+                    }
+                    None => template_value,
+                };
+                profile.insert(k, merged);
+            }
+            serde_yaml::Value::Mapping(profile)
+        }
+        (serde_yaml::Value::Sequence(template), serde_yaml::Value::Sequence(profile)) => match key {
+            Some("rules") => serde_yaml::Value::Sequence(merge_rules(template, profile, rules_first)),
+            Some(k) if NAMED_LIST_KEYS.contains(&k) => {
+                serde_yaml::Value::Sequence(merge_named_list(template, profile))
+            }
+            _ => serde_yaml::Value::Sequence(profile),
+        },
+        (_, profile) => profile,
+    }
+}
+
+/// Layer the base template (if any) under `yaml`
+fn apply_base_template(yaml: &mut serde_yaml::Value) {
+    let Some(template) = load_base_template() else {
+        return;
+    };
+    let rules_first = crate::user_overrides::load_overrides()
+        .base_template_rules_first
+        .unwrap_or(false);
+
+    let profile = std::mem::take(yaml);
+    *yaml = merge_template_value(template, profile, None, rules_first);
+}
+
+/// Get the current base template, if one is set
+#[tauri::command]
+pub fn get_base_template() -> Result<Option<String>, String> {
+    let path = get_base_template_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(&path)
+        .map(Some)
+        .map_err(|e| format!("Failed to read base template: {}", e))
+}
+
+/// Save the base template merged under every profile and reload the core.
+/// `rules_first` puts the template's rules ahead of the profile's; when omitted the
+/// saved preference is kept.
+#[tauri::command]
+pub async fn set_base_template(
+    app: tauri::AppHandle,
+    state: State<'_, MihomoState>,
+    yaml: String,
+    rules_first: Option<bool>,
+) -> Result<(), String> {
+    let parsed: serde_yaml::Value =
+        serde_yaml::from_str(&yaml).map_err(|e| format!("Invalid YAML in base template: {}", e))?;
+    if !parsed.is_mapping() {
+        return Err("Base template must be a YAML mapping".to_string());
+    }
+
+    let path = get_base_template_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    snapshot_config(&path, "template", &yaml);
+    std::fs::write(&path, &yaml).map_err(|e| format!("Failed to save base template: {}", e))?;
+    if let Some(rules_first) = rules_first {
+        crate::user_overrides::persist_base_template_rules_first(Some(rules_first))?;
+    }
+    tracing::info!("Base template saved to {:?}", path);
+
+    if is_core_running(state.inner()) {
+        restart_core_with_overrides(&app, state.clone()).await?;
+    }
+    Ok(())
+}
+
+/// Remove the base template and reload the core
+#[tauri::command]
+pub async fn clear_base_template(
+    app: tauri::AppHandle,
+    state: State<'_, MihomoState>,
+) -> Result<(), String> {
+    let path = get_base_template_path();
+    if !path.exists() {
+        return Ok(());
+    }
+
+    snapshot_config(&path, "template", "");
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to remove base template: {}", e))?;
+    tracing::info!("Base template cleared");
+
+    if is_core_running(state.inner()) {
+        restart_core_with_overrides(&app, state.clone()).await?;
+    }
+    Ok(())
+}
@@ -0,0 +1,134 @@
+// ========== Installed Cores ==========
+//
+// Extra Mihomo builds can be dropped into `<data dir>/aqiu/cores/` and switched
+// between with `select_core`. With nothing selected (or an empty directory) the
+// default single binary is used.
+
+const DEFAULT_CORE_NAME: &str = "default";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InstalledCore {
+    /// File name under `cores/`, or "default" for the default binary
+    pub name: String,
+    pub path: String,
+    pub version: Option<String>,
+    pub selected: bool,
+}
+
+fn get_cores_dir() -> PathBuf {
+    let app_data = dirs::data_local_dir().unwrap_or_default();
+    app_data.join("aqiu").join("cores")
+}
+
+fn is_core_binary(path: &std::path::Path) -> bool {
+    let Ok(metadata) = std::fs::metadata(path) else {
+        return false;
+    };
+    if !metadata.is_file() {
+        return false;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(windows)]
+    {
+        path.extension().map(|ext| ext.eq_ignore_ascii_case("exe")).unwrap_or(false)
+    }
+}
+
+/// Core binaries in `cores/`, sorted by name
+fn list_core_binaries() -> Vec<(String, PathBuf)> {
+    let mut cores: Vec<(String, PathBuf)> = std::fs::read_dir(get_cores_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| (entry.file_name().to_string_lossy().to_string(), entry.path()))
+                .filter(|(_, path)| is_core_binary(path))
+                .collect()
+        })
+        .unwrap_or_default();
+    cores.sort_by(|a, b| a.0.cmp(&b.0));
+    cores
+}
+
+/// Path of the core chosen with `select_core`, if it is still installed
+fn selected_core_path() -> Option<PathBuf> {
+    let name = crate::user_overrides::load_overrides().core_binary?;
+    let path = get_cores_dir().join(&name);
+    if is_core_binary(&path) {
+        Some(path)
+    } else {
+        tracing::warn!("Selected core {} is no longer installed, using the default binary", name);
+        None
+    }
+}
+
+/// List the default binary and the cores in `cores/` with their `-v` versions
+#[tauri::command]
+pub async fn list_installed_cores() -> Result<Vec<InstalledCore>, String> {
+    let selected = selected_core_path();
+    let mut cores = Vec::new();
+
+    let default_path = get_default_mihomo_path();
+    if default_path.exists() {
+        cores.push(InstalledCore {
+            name: DEFAULT_CORE_NAME.to_string(),
+            path: default_path.to_string_lossy().to_string(),
+            version: get_core_binary_version(&default_path).await,
+            selected: selected.is_none(),
+        });
+    }
+
+    for (name, path) in list_core_binaries() {
+        cores.push(InstalledCore {
+            version: get_core_binary_version(&path).await,
+            selected: selected.as_ref() == Some(&path),
+            path: path.to_string_lossy().to_string(),
+            name,
+        });
+    }
+
+    Ok(cores)
+}
+
+/// Run the core named `name` from `cores/` ("default" for the default binary),
+/// restarting it if it is running. Service Mode keeps the binary it was installed
+/// with until it is reinstalled.
+#[tauri::command]
+pub async fn select_core(
+    app: tauri::AppHandle,
+    state: State<'_, MihomoState>,
+    name: String,
+) -> Result<(), String> {
+    let name = name.trim().to_string();
+    let selection = if name.is_empty() || name == DEFAULT_CORE_NAME {
+        None
+    } else {
+        if name.contains('/') || name.contains('\\') || name.contains("..") {
+            return Err("Invalid core name".to_string());
+        }
+        if !is_core_binary(&get_cores_dir().join(&name)) {
+            return Err(format!("Core {} is not installed in {:?}", name, get_cores_dir()));
+        }
+        Some(name)
+    };
+
+    let previous = crate::user_overrides::load_overrides().core_binary;
+    if previous == selection {
+        return Ok(());
+    }
+
+    crate::user_overrides::persist_core_binary(selection.clone())?;
+    tracing::info!(
+        "Selected core: {}",
+        selection.as_deref().unwrap_or(DEFAULT_CORE_NAME)
+    );
+
+    if is_core_running(state.inner()) {
+        restart_core_with_overrides(&app, state.clone()).await?;
+    }
+    Ok(())
+}
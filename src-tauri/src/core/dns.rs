@@ -0,0 +1,337 @@
+// ========== DNS / Fake-IP ==========
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FakeIpFilterInfo {
+    /// Entries declared by the active profile itself
+    pub base: Vec<String>,
+    /// Proxy server domains auto-added to avoid Fake-IP dialing (TUN only)
+    pub proxy_domains: Vec<String>,
+    /// User entries stored in overrides
+    pub user_entries: Vec<String>,
+    /// Final list after applying overrides, as the core will see it
+    pub effective: Vec<String>,
+}
+
+fn read_string_list(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    value
+        .and_then(|v| v.as_sequence())
+        .map(|seq| {
+            seq.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Load the active profile config as YAML (falls back to the default config)
+fn load_active_profile_yaml() -> Result<serde_yaml::Value, String> {
+    let config_path = crate::profiles::get_active_profile_path()
+        .ok()
+        .flatten()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| get_config_dir().join("config.yaml"));
+
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config {:?}: {}", config_path, e))?;
+    serde_yaml::from_str(&content).map_err(|e| format!("Invalid YAML in config: {}", e))
+}
+
+/// Get the effective `dns.fake-ip-filter` list for the active profile
+#[tauri::command]
+pub fn get_fake_ip_filter() -> Result<FakeIpFilterInfo, String> {
+    let mut profile_yaml = load_active_profile_yaml()?;
+    let overrides = crate::user_overrides::load_overrides();
+
+    // The template's entries are part of the base the core sees, not proxy domains
+    apply_base_template(&mut profile_yaml);
+
+    let base = read_string_list(
+        profile_yaml
+            .get("dns")
+            .and_then(|dns| dns.get("fake-ip-filter")),
+    );
+
+    let mut merged = profile_yaml.clone();
+    crate::user_overrides::apply_overrides_to_yaml(&mut merged, &overrides)?;
+    let effective = read_string_list(merged.get("dns").and_then(|dns| dns.get("fake-ip-filter")));
+
+    let proxy_domains = profile_yaml
+        .as_mapping()
+        .map(crate::user_overrides::collect_proxy_server_domains)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|d| effective.contains(d) && !base.contains(d))
+        .collect();
+
+    Ok(FakeIpFilterInfo {
+        base,
+        proxy_domains,
+        user_entries: overrides.fake_ip_filter.unwrap_or_default(),
+        effective,
+    })
+}
+
+/// Replace the user's extra fake-ip-filter entries and reload the core if they changed
+#[tauri::command]
+pub async fn set_fake_ip_filter_user_entries(
+    app: tauri::AppHandle,
+    state: State<'_, MihomoState>,
+    entries: Vec<String>,
+) -> Result<FakeIpFilterInfo, String> {
+    let previous = crate::user_overrides::load_overrides()
+        .fake_ip_filter
+        .unwrap_or_default();
+    let saved = crate::user_overrides::persist_fake_ip_filter_entries(entries)?;
+
+    // DNS settings can't be PATCHed, so a change needs the config to be reloaded
+    if saved != previous && is_core_running(state.inner()) {
+        tracing::info!("fake-ip-filter changed, reloading core...");
+        restart_core_with_overrides(&app, state.clone()).await?;
+    }
+
+    get_fake_ip_filter()
+}
+
+// ========== DNS Diagnosis ==========
+
+const FORCE_DOH_NAMESERVERS: [&str; 3] = [
+    "https://223.5.5.5/dns-query",
+    "https://1.12.12.12/dns-query",
+    "https://1.1.1.1/dns-query",
+];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProxyServerDnsCheck {
+    pub domain: String,
+    /// Resolved through the system resolver (hijacked by the core under TUN)
+    pub resolvable: bool,
+    pub addresses: Vec<String>,
+    /// Resolved into `dns.fake-ip-range`, so dialing the proxy server will fail
+    pub fake_ip: bool,
+    pub in_fake_ip_filter: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DnsDiagnosis {
+    pub dns_enabled: bool,
+    pub enhanced_mode: Option<String>,
+    pub proxy_server_nameserver: Vec<String>,
+    pub servers: Vec<ProxyServerDnsCheck>,
+    /// Strategies for `apply_dns_fix`, most relevant first
+    pub suggested_strategies: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DnsFixResult {
+    pub strategy: String,
+    /// Human-readable list of what was changed
+    pub changes: Vec<String>,
+    pub restarted: bool,
+}
+
+fn ipv4_in_cidr(ip: std::net::Ipv4Addr, cidr: &str) -> bool {
+    let Some((base, prefix)) = cidr.split_once('/') else {
+        return false;
+    };
+    let (Ok(base), Ok(prefix)) = (base.parse::<std::net::Ipv4Addr>(), prefix.parse::<u32>()) else {
+        return false;
+    };
+    if prefix > 32 {
+        return false;
+    }
+    let mask = if prefix == 0 { 0 } else { u32::MAX << (32 - prefix) };
+    (u32::from(ip) & mask) == (u32::from(base) & mask)
+}
+
+/// Match a domain against fake-ip-filter entries (exact, `+.` and `*.` wildcards)
+fn fake_ip_filter_matches(filter: &[String], domain: &str) -> bool {
+    filter.iter().any(|entry| {
+        if let Some(suffix) = entry.strip_prefix("+.") {
+            domain == suffix || domain.ends_with(&format!(".{}", suffix))
+        } else if let Some(suffix) = entry.strip_prefix("*.") {
+            domain
+                .strip_suffix(&format!(".{}", suffix))
+                .map(|label| !label.is_empty() && !label.contains('.'))
+                .unwrap_or(false)
+        } else {
+            entry == domain
+        }
+    })
+}
+
+/// Effective config (active profile + overrides) as the core will see it
+fn load_effective_config_yaml() -> Result<serde_yaml::Value, String> {
+    let mut yaml = load_active_profile_yaml()?;
+    apply_base_template(&mut yaml);
+    let overrides = crate::user_overrides::load_overrides();
+    crate::user_overrides::apply_overrides_to_yaml(&mut yaml, &overrides)?;
+    Ok(yaml)
+}
+
+/// Proxy server domains of the active profile (without the `+.` wildcard forms)
+fn active_proxy_server_domains() -> Result<Vec<String>, String> {
+    let profile_yaml = load_active_profile_yaml()?;
+    Ok(profile_yaml
+        .as_mapping()
+        .map(crate::user_overrides::collect_proxy_server_domains)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|d| !d.starts_with("+."))
+        .collect())
+}
+
+/// Check whether proxy server domains resolve and whether they end up as Fake-IPs,
+/// the usual causes of `dns resolve failed: couldn't find ip`
+#[tauri::command]
+pub async fn diagnose_dns() -> Result<DnsDiagnosis, String> {
+    let effective = load_effective_config_yaml()?;
+    let dns = effective.get("dns");
+
+    let dns_enabled = dns
+        .and_then(|d| d.get("enable"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let enhanced_mode = dns
+        .and_then(|d| d.get("enhanced-mode"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+    let fake_ip_range = dns
+        .and_then(|d| d.get("fake-ip-range"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("198.18.0.1/16")
+        .to_string();
+    let fake_ip_filter = read_string_list(dns.and_then(|d| d.get("fake-ip-filter")));
+    let proxy_server_nameserver =
+        read_string_list(dns.and_then(|d| d.get("proxy-server-nameserver")));
+
+    let mut servers = Vec::new();
+    for domain in active_proxy_server_domains()? {
+        let lookup = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            tokio::net::lookup_host((domain.as_str(), 0)),
+        )
+        .await;
+
+        let addresses: Vec<std::net::IpAddr> = match lookup {
+            Ok(Ok(addrs)) => addrs.map(|a| a.ip()).collect(),
+            _ => Vec::new(),
+        };
+
+        let fake_ip = addresses.iter().any(|ip| match ip {
+            std::net::IpAddr::V4(v4) => ipv4_in_cidr(*v4, &fake_ip_range),
+            std::net::IpAddr::V6(_) => false,
+        });
+
+        servers.push(ProxyServerDnsCheck {
+            in_fake_ip_filter: fake_ip_filter_matches(&fake_ip_filter, &domain),
+            resolvable: !addresses.is_empty(),
+            addresses: addresses.iter().map(|ip| ip.to_string()).collect(),
+            fake_ip,
+            domain,
+        });
+    }
+
+    let mut suggested_strategies = Vec::new();
+    let fake_ip_mode = dns_enabled && enhanced_mode.as_deref() == Some("fake-ip");
+    if servers.iter().any(|s| s.fake_ip || (fake_ip_mode && !s.in_fake_ip_filter)) {
+        suggested_strategies.push("add-to-fake-ip-filter".to_string());
+    }
+    if servers.iter().any(|s| !s.resolvable) {
+        // DoH already in use and still failing: the network likely blocks it
+        let uses_doh = proxy_server_nameserver.iter().all(|ns| ns.starts_with("https://"))
+            && !proxy_server_nameserver.is_empty();
+        if uses_doh {
+            suggested_strategies.push("use-system-dns".to_string());
+            suggested_strategies.push("force-doh".to_string());
+        } else {
+            suggested_strategies.push("force-doh".to_string());
+            suggested_strategies.push("use-system-dns".to_string());
+        }
+    }
+
+    tracing::info!(
+        "diagnose_dns: {} proxy server domains, suggestions: {:?}",
+        servers.len(),
+        suggested_strategies
+    );
+
+    Ok(DnsDiagnosis {
+        dns_enabled,
+        enhanced_mode,
+        proxy_server_nameserver,
+        servers,
+        suggested_strategies,
+    })
+}
+
+/// Apply a DNS fix strategy through the overrides and reload the core:
+/// - `force-doh`: resolve proxy servers over IP-addressed DoH (no bootstrap, bypasses dns-hijack)
+/// - `add-to-fake-ip-filter`: keep proxy server domains out of Fake-IP
+/// - `use-system-dns`: resolve proxy servers with the system resolver
+/// - `reset`: drop the `proxy-server-nameserver` override and use the profile's again
+#[tauri::command]
+pub async fn apply_dns_fix(
+    app: tauri::AppHandle,
+    state: State<'_, MihomoState>,
+    strategy: String,
+) -> Result<DnsFixResult, String> {
+    let mut changes = Vec::new();
+
+    match strategy.as_str() {
+        "force-doh" => {
+            let nameservers: Vec<String> =
+                FORCE_DOH_NAMESERVERS.iter().map(|s| s.to_string()).collect();
+            crate::user_overrides::persist_proxy_server_nameserver(Some(nameservers.clone()))?;
+            changes.push(format!("proxy-server-nameserver set to {}", nameservers.join(", ")));
+        }
+        "use-system-dns" => {
+            crate::user_overrides::persist_proxy_server_nameserver(Some(vec!["system".to_string()]))?;
+            changes.push("proxy-server-nameserver set to system".to_string());
+        }
+        "reset" => {
+            let previous = crate::user_overrides::load_overrides().proxy_server_nameserver;
+            if let Some(previous) = previous {
+                crate::user_overrides::persist_proxy_server_nameserver(None)?;
+                changes.push(format!(
+                    "proxy-server-nameserver override ({}) removed",
+                    previous.join(", ")
+                ));
+            }
+        }
+        "add-to-fake-ip-filter" => {
+            let mut entries = crate::user_overrides::load_overrides()
+                .fake_ip_filter
+                .unwrap_or_default();
+            let before = entries.len();
+            for domain in active_proxy_server_domains()? {
+                let wildcard = format!("+.{}", domain);
+                if !entries.contains(&domain) && !entries.contains(&wildcard) {
+                    changes.push(format!("added {} to fake-ip-filter", domain));
+                    entries.push(domain);
+                }
+            }
+            if entries.len() != before {
+                crate::user_overrides::persist_fake_ip_filter_entries(entries)?;
+            }
+        }
+        _ => {
+            return Err(format!(
+                "Unknown DNS fix strategy: {} (expected force-doh, add-to-fake-ip-filter, use-system-dns or reset)",
+                strategy
+            ))
+        }
+    }
+
+    // DNS settings can't be PATCHed, so the core needs a reload to pick them up
+    let restarted = !changes.is_empty() && is_core_running(state.inner());
+    if restarted {
+        tracing::info!("apply_dns_fix: {} applied, reloading core...", strategy);
+        restart_core_with_overrides(&app, state.clone()).await?;
+    }
+
+    Ok(DnsFixResult {
+        strategy,
+        changes,
+        restarted,
+    })
+}
@@ -0,0 +1,219 @@
+// ========== GEO Database ==========
+
+const GEODATA_FILES: [&str; 2] = ["geoip.dat", "geosite.dat"];
+const GEODATA_DOWNLOAD_BASE: &str =
+    "https://github.com/MetaCubeX/meta-rules-dat/releases/download/latest";
+const DEFAULT_GEODATA_UPDATE_INTERVAL_DAYS: u32 = 7;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GeodataFileStatus {
+    pub name: String,
+    pub path: String,
+    pub exists: bool,
+    pub size: Option<u64>,
+    pub updated_at: Option<String>,
+    pub age_days: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GeodataStatus {
+    pub files: Vec<GeodataFileStatus>,
+    pub interval_days: u32,
+    /// All files exist and are younger than `interval_days`
+    pub fresh: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GeodataUpdatedEvent {
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GeodataUpdateFailedEvent {
+    pub error: String,
+}
+
+/// Directory Mihomo uses as its home (`-d`), where it looks for geodata files
+fn get_geodata_dir(state: &MihomoState) -> PathBuf {
+    resolve_config_path(state)
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(get_config_dir)
+}
+
+fn geodata_update_interval_days() -> u32 {
+    crate::user_overrides::load_overrides()
+        .geodata_update_interval_days
+        .unwrap_or(DEFAULT_GEODATA_UPDATE_INTERVAL_DAYS)
+}
+
+fn collect_geodata_status(dir: &std::path::Path, interval_days: u32) -> GeodataStatus {
+    let mut files = Vec::with_capacity(GEODATA_FILES.len());
+    let mut fresh = true;
+
+    for name in GEODATA_FILES {
+        let path = dir.join(name);
+        let metadata = std::fs::metadata(&path).ok();
+        let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+        let age_days = modified
+            .and_then(|m| m.elapsed().ok())
+            .map(|d| d.as_secs() / 86_400);
+
+        if age_days.map(|age| age >= interval_days as u64).unwrap_or(true) {
+            fresh = false;
+        }
+
+        files.push(GeodataFileStatus {
+            name: name.to_string(),
+            path: path.to_string_lossy().to_string(),
+            exists: metadata.is_some(),
+            size: metadata.as_ref().map(|m| m.len()),
+            updated_at: modified.map(|m| {
+                let time: chrono::DateTime<chrono::Local> = m.into();
+                time.format("%Y-%m-%d %H:%M:%S").to_string()
+            }),
+            age_days,
+        });
+    }
+
+    GeodataStatus {
+        files,
+        interval_days,
+        fresh,
+    }
+}
+
+/// Get existence and age of the GeoIP/GeoSite files used by the core
+#[tauri::command]
+pub fn get_geodata_status(state: State<'_, MihomoState>) -> Result<GeodataStatus, String> {
+    let dir = get_geodata_dir(state.inner());
+    Ok(collect_geodata_status(&dir, geodata_update_interval_days()))
+}
+
+/// Ask the running core to update its geodata via the official /upgrade/geo API
+async fn update_geodata_via_api(state: &MihomoState) -> Result<(), String> {
+    let (api_host, api_port, api_secret) = {
+        let host = state.api_host.lock().map_err(|e| e.to_string())?.clone();
+        let port = *state.api_port.lock().map_err(|e| e.to_string())?;
+        let secret = get_api_secret_from_state(state);
+        (host, port, secret)
+    };
+
+    let url = format!("http://{}:{}/upgrade/geo", api_host, api_port);
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(300))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    // Send empty JSON body as required by the API
+    let request = add_auth_header(
+        client.post(&url).json(&serde_json::json!({})),
+        api_secret.as_deref()
+    );
+
+    tracing::info!("Updating GEO database via API: {}", url);
+
+    let response = request.send().await
+        .map_err(|e| format!("Failed to send GEO update request: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(format!("GEO update failed: {} - {}", status, error_text));
+    }
+
+    Ok(())
+}
+
+/// Download geodata files directly from the meta-rules-dat release into `dir`
+async fn download_geodata_direct(dir: &std::path::Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(300))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    for name in GEODATA_FILES {
+        let url = format!("{}/{}", GEODATA_DOWNLOAD_BASE, name);
+        tracing::info!("Downloading {} from {}", name, url);
+
+        let response = client
+            .get(&url)
+            .header("User-Agent", "AQiu-Proxy")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to download {}: {}", name, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to download {}: {}", name, response.status()));
+        }
+
+        let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+
+        // Write to a temp file first so a partial download never replaces a good file
+        let target = dir.join(name);
+        let temp = dir.join(format!("{}.download", name));
+        std::fs::write(&temp, &bytes)
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+        std::fs::rename(&temp, &target)
+            .map_err(|e| format!("Failed to replace {}: {}", name, e))?;
+    }
+
+    Ok(())
+}
+
+/// Update geodata through the running core, falling back to a direct download
+/// when the core is not running or the API request fails.
+async fn update_geodata_inner(state: &MihomoState) -> Result<String, String> {
+    if is_core_running(state) {
+        match update_geodata_via_api(state).await {
+            Ok(()) => return Ok("GEO database updated successfully via mihomo API".to_string()),
+            Err(e) => tracing::warn!("GEO update via API failed, falling back to direct download: {}", e),
+        }
+    }
+
+    let dir = get_geodata_dir(state);
+    download_geodata_direct(&dir).await?;
+    Ok(format!("GEO database downloaded to {}", dir.to_string_lossy()))
+}
+
+/// Background task: periodically refresh geodata when it is older than
+/// `geodata-update-interval-days` (default weekly, 0 disables). Deferred while
+/// auto-updates are paused or the connection is metered.
+pub async fn run_geodata_auto_update(app: tauri::AppHandle) {
+    use tauri::Manager;
+
+    // Give the core time to start before the first check
+    tokio::time::sleep(tokio::time::Duration::from_secs(60)).await;
+
+    loop {
+        let mut next_check_secs = 3600;
+        let interval_days = geodata_update_interval_days();
+        if interval_days > 0 {
+            let state = app.state::<MihomoState>();
+            let status = collect_geodata_status(&get_geodata_dir(state.inner()), interval_days);
+
+            if status.fresh {
+                tracing::info!("GEO auto-update: geodata is fresh, skipping");
+            } else if defer_auto_update(&app, "geodata") {
+                // Check back sooner so the update runs once the pause ends
+                next_check_secs = 600;
+            } else {
+                tracing::info!("GEO auto-update: geodata is stale or missing, updating...");
+                match update_geodata_inner(state.inner()).await {
+                    Ok(message) => {
+                        let _ = app.emit("geodata-updated", GeodataUpdatedEvent { message });
+                    }
+                    Err(error) => {
+                        tracing::error!("GEO auto-update failed: {}", error);
+                        let _ = app.emit("geodata-update-failed", GeodataUpdateFailedEvent { error });
+                    }
+                }
+            }
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(next_check_secs)).await;
+    }
+}
@@ -0,0 +1,60 @@
+// ========== Kill Switch ==========
+
+#[derive(Debug, Serialize, Clone)]
+pub struct KillSwitchStatus {
+    pub enabled: bool,
+    /// Whether the service is currently blocking outbound traffic
+    pub blocking: bool,
+}
+
+/// Push the kill-switch preference to the service.
+/// `armed` is false after an intentional stop so only unexpected exits are blocked;
+/// the service watches the controller port and reconciles rules on its own after a crash.
+async fn sync_kill_switch(state: &MihomoState, armed: bool) -> Result<bool, String> {
+    let enabled = armed && crate::user_overrides::load_overrides().kill_switch.unwrap_or(false);
+
+    if !crate::service::is_service_available().await {
+        if enabled {
+            return Err("Kill-switch requires the AQiu service".to_string());
+        }
+        return Ok(false);
+    }
+
+    crate::service::ensure_compatible_service().await?;
+
+    let api_port = *state.api_port.lock().map_err(|e| e.to_string())?;
+    crate::service::set_kill_switch(enabled, Some(api_port)).await
+}
+
+/// Arm the kill-switch after a successful (re)start. Restarts go through
+/// `stop_core_inner`, which disarms it, so every restart path must call this.
+async fn rearm_kill_switch(state: &MihomoState) {
+    if let Err(e) = sync_kill_switch(state, true).await {
+        tracing::warn!("Failed to arm kill-switch: {}", e);
+    }
+}
+
+/// Enable or disable the kill-switch that blocks outbound traffic while the core is down
+#[tauri::command]
+pub async fn set_kill_switch(
+    state: State<'_, MihomoState>,
+    enable: bool,
+) -> Result<KillSwitchStatus, String> {
+    crate::service::ensure_compatible_service().await?;
+
+    if enable && !crate::service::is_service_available().await {
+        return Err("Kill-switch requires the AQiu service to be installed".to_string());
+    }
+
+    crate::user_overrides::persist_kill_switch(enable)?;
+
+    // Only arm while the core is up, otherwise enabling it would cut the network right away
+    let blocking = sync_kill_switch(state.inner(), is_core_running(state.inner())).await?;
+
+    tracing::info!("Kill-switch {}: blocking={}", if enable { "enabled" } else { "disabled" }, blocking);
+
+    Ok(KillSwitchStatus {
+        enabled: enable,
+        blocking,
+    })
+}
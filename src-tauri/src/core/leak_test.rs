@@ -0,0 +1,251 @@
+// ========== Leak Test ==========
+
+/// Plain-text "what is my IP" endpoints, tried in order
+const DEFAULT_IP_ENDPOINTS: &[&str] = &[
+    "https://api.ipify.org",
+    "https://ifconfig.me/ip",
+    "https://icanhazip.com",
+];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DnsLeakCheck {
+    pub domain: String,
+    pub addresses: Vec<String>,
+    /// Whether the system resolver got its answer from the core (a Fake-IP)
+    pub answered_by_core: bool,
+    /// None when the core isn't in Fake-IP mode, so leaks can't be told apart
+    pub leaking: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LeakTestResult {
+    pub endpoint: String,
+    pub proxy_port: u16,
+    pub direct_ip: Option<String>,
+    pub proxy_ip: Option<String>,
+    pub direct_error: Option<String>,
+    pub proxy_error: Option<String>,
+    /// Both IPs are known and differ
+    pub ips_differ: bool,
+    /// With TUN on the "direct" request is captured as well, so equal IPs are expected
+    pub tun_enabled: bool,
+    pub dns: Option<DnsLeakCheck>,
+    pub passed: bool,
+}
+
+/// Port of the local HTTP proxy as reported by the running core (mixed-port first)
+fn proxy_port_from_running_config(config: &serde_json::Value) -> Option<u16> {
+    ["mixed-port", "port"]
+        .iter()
+        .filter_map(|key| config.get(*key).and_then(|v| v.as_u64()))
+        .find(|port| *port > 0)
+        .map(|port| port as u16)
+}
+
+async fn fetch_public_ip(client: &reqwest::Client, endpoints: &[String]) -> Result<String, String> {
+    let mut last_error = "No IP endpoint configured".to_string();
+    for endpoint in endpoints {
+        let response = match client.get(endpoint).send().await {
+            Ok(response) if response.status().is_success() => response,
+            Ok(response) => {
+                last_error = format!("{} returned {}", endpoint, response.status());
+                continue;
+            }
+            Err(e) => {
+                last_error = format!("{}: {}", endpoint, e);
+                continue;
+            }
+        };
+
+        let body = response.text().await.map_err(|e| e.to_string())?;
+        let ip = body.trim();
+        if ip.parse::<std::net::IpAddr>().is_ok() {
+            return Ok(ip.to_string());
+        }
+        last_error = format!("{} returned an unexpected body", endpoint);
+    }
+    Err(last_error)
+}
+
+/// Fake-IP range from the effective config's `dns:` section, or None when the core
+/// doesn't run in Fake-IP mode. `GET /configs` has no `dns` key, so this reads the file.
+fn fake_ip_range_from_config(config: &serde_yaml::Value) -> Option<String> {
+    let dns = config.get("dns")?;
+    if dns.get("enhanced-mode").and_then(|v| v.as_str()) != Some("fake-ip") {
+        return None;
+    }
+    Some(
+        dns.get("fake-ip-range")
+            .and_then(|v| v.as_str())
+            .unwrap_or("198.18.0.1/16")
+            .to_string(),
+    )
+}
+
+fn evaluate_dns_answers(
+    domain: String,
+    addresses: &[std::net::IpAddr],
+    fake_ip_range: Option<&str>,
+) -> DnsLeakCheck {
+    let answered_by_core = fake_ip_range.is_some_and(|range| {
+        addresses.iter().any(|ip| match ip {
+            std::net::IpAddr::V4(v4) => ipv4_in_cidr(*v4, range),
+            std::net::IpAddr::V6(_) => false,
+        })
+    });
+
+    DnsLeakCheck {
+        domain,
+        addresses: addresses.iter().map(|ip| ip.to_string()).collect(),
+        answered_by_core,
+        leaking: fake_ip_range.map(|_| !answered_by_core),
+    }
+}
+
+async fn check_dns_leak(fake_ip_range: Option<&str>, domain: String) -> DnsLeakCheck {
+    let lookup = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        tokio::net::lookup_host((domain.as_str(), 0)),
+    )
+    .await;
+    let addresses: Vec<std::net::IpAddr> = match lookup {
+        Ok(Ok(addrs)) => addrs.map(|a| a.ip()).collect(),
+        _ => Vec::new(),
+    };
+
+    evaluate_dns_answers(domain, &addresses, fake_ip_range)
+}
+
+/// Fetch the public IP directly and through the proxy port and compare them.
+/// `endpoint` overrides the IP lookup service; pass `dns_test_domain` to also check
+/// whether system DNS queries are answered by the core.
+#[tauri::command]
+pub async fn run_leak_test(
+    state: State<'_, MihomoState>,
+    endpoint: Option<String>,
+    dns_test_domain: Option<String>,
+) -> Result<LeakTestResult, String> {
+    if !is_core_running(state.inner()) {
+        return Err("Core is not running".to_string());
+    }
+
+    let config = fetch_running_config(state.inner()).await?;
+    let proxy_port =
+        proxy_port_from_running_config(&config).ok_or("The core has no HTTP/mixed proxy port enabled")?;
+
+    let endpoints: Vec<String> = match endpoint.map(|e| e.trim().to_string()).filter(|e| !e.is_empty()) {
+        Some(endpoint) => vec![endpoint],
+        None => DEFAULT_IP_ENDPOINTS.iter().map(|e| e.to_string()).collect(),
+    };
+    let timeout = std::time::Duration::from_secs(10);
+
+    // `no_proxy` keeps reqwest from picking up the system proxy for the direct leg
+    let direct_client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let proxy = reqwest::Proxy::all(format!("http://127.0.0.1:{}", proxy_port))
+        .map_err(|e| e.to_string())?;
+    let proxy_client = reqwest::Client::builder()
+        .proxy(proxy)
+        .timeout(timeout)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let (direct, proxied) = tokio::join!(
+        fetch_public_ip(&direct_client, &endpoints),
+        fetch_public_ip(&proxy_client, &endpoints)
+    );
+
+    let dns = match dns_test_domain.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()) {
+        Some(domain) => {
+            let fake_ip_range = match load_effective_config_yaml() {
+                Ok(effective) => fake_ip_range_from_config(&effective),
+                Err(e) => {
+                    tracing::warn!("Leak test: can't read the DNS settings: {}", e);
+                    None
+                }
+            };
+            Some(check_dns_leak(fake_ip_range.as_deref(), domain).await)
+        }
+        None => None,
+    };
+
+    let ips_differ = matches!((&direct, &proxied), (Ok(d), Ok(p)) if d != p);
+    let dns_ok = dns.as_ref().and_then(|d| d.leaking) != Some(true);
+    let tun_enabled = config
+        .get("tun")
+        .and_then(|t| t.get("enable"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let passed = dns_ok && if tun_enabled { proxied.is_ok() } else { ips_differ };
+
+    tracing::info!(
+        "Leak test: direct={:?} proxy={:?} passed={}",
+        direct,
+        proxied,
+        passed
+    );
+
+    Ok(LeakTestResult {
+        endpoint: endpoints.join(", "),
+        proxy_port,
+        direct_ip: direct.as_ref().ok().cloned(),
+        proxy_ip: proxied.as_ref().ok().cloned(),
+        direct_error: direct.err(),
+        proxy_error: proxied.err(),
+        ips_differ,
+        tun_enabled,
+        dns,
+        passed,
+    })
+}
+
+#[cfg(test)]
+mod leak_test_tests {
+    use super::*;
+
+    fn yaml(content: &str) -> serde_yaml::Value {
+        serde_yaml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn fake_ip_range_is_read_from_dns_section() {
+        let config = yaml("dns:\n  enhanced-mode: fake-ip\n  fake-ip-range: 28.0.0.1/8\n");
+        assert_eq!(fake_ip_range_from_config(&config).as_deref(), Some("28.0.0.1/8"));
+
+        let default_range = yaml("dns:\n  enhanced-mode: fake-ip\n");
+        assert_eq!(
+            fake_ip_range_from_config(&default_range).as_deref(),
+            Some("198.18.0.1/16")
+        );
+
+        let redir_host = yaml("dns:\n  enhanced-mode: redir-host\n");
+        assert_eq!(fake_ip_range_from_config(&redir_host), None);
+        assert_eq!(fake_ip_range_from_config(&yaml("mixed-port: 7890\n")), None);
+    }
+
+    #[test]
+    fn real_answer_in_fake_ip_mode_is_a_leak() {
+        let config = yaml("dns:\n  enhanced-mode: fake-ip\n  fake-ip-range: 198.18.0.1/16\n");
+        let range = fake_ip_range_from_config(&config);
+
+        let fake = ["198.18.0.42".parse().unwrap()];
+        let check = evaluate_dns_answers("example.com".into(), &fake, range.as_deref());
+        assert!(check.answered_by_core);
+        assert_eq!(check.leaking, Some(false));
+
+        let real = ["93.184.216.34".parse().unwrap()];
+        let check = evaluate_dns_answers("example.com".into(), &real, range.as_deref());
+        assert!(!check.answered_by_core);
+        assert_eq!(check.leaking, Some(true));
+    }
+
+    #[test]
+    fn leak_is_unknown_without_fake_ip() {
+        let real = ["93.184.216.34".parse().unwrap()];
+        let check = evaluate_dns_answers("example.com".into(), &real, None);
+        assert_eq!(check.leaking, None);
+    }
+}
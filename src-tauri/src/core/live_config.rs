@@ -0,0 +1,328 @@
+// ========== Live Override Application ==========
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct LiveApplyResult {
+    /// Reloadable keys that changed (applied via PATCH /configs unless a restart was needed)
+    pub applied: Vec<String>,
+    /// Keys that changed but cannot be hot-reloaded (listen ports, controller, TUN enable)
+    pub restart_required: Vec<String>,
+    /// Whether the core was restarted to apply `restart_required`
+    pub restarted: bool,
+}
+
+/// Fetch the running core's configuration via GET /configs
+async fn fetch_running_config(state: &MihomoState) -> Result<serde_json::Value, String> {
+    let (api_host, api_port, api_secret) = {
+        let host = state.api_host.lock().map_err(|e| e.to_string())?.clone();
+        let port = *state.api_port.lock().map_err(|e| e.to_string())?;
+        let secret = get_api_secret_from_state(state);
+        (host, port, secret)
+    };
+
+    let url = format!("http://{}:{}/configs", api_host, api_port);
+
+    let client = reqwest::Client::new();
+    let request = add_auth_header(
+        client.get(&url).timeout(std::time::Duration::from_secs(5)),
+        api_secret.as_deref()
+    );
+    let response = request.send().await
+        .map_err(|e| format!("Failed to get running config: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Failed to get running config: {}", response.status()));
+    }
+
+    response.json().await.map_err(|e| e.to_string())
+}
+
+/// Split the differences between the overrides and the running config into a
+/// PATCH payload (hot-reloadable keys) and a list of keys that need a restart.
+fn diff_overrides_against_running(
+    overrides: &crate::user_overrides::UserConfigOverrides,
+    running: &serde_json::Value,
+    controller: (&str, u16),
+) -> (serde_json::Map<String, serde_json::Value>, Vec<String>, Vec<String>) {
+    let mut patch = serde_json::Map::new();
+    let mut applied = Vec::new();
+    let mut restart_required = Vec::new();
+
+    // Listen ports are bound at startup; changing them requires a restart.
+    let ports = [
+        ("port", overrides.port),
+        ("socks-port", overrides.socks_port),
+        ("mixed-port", overrides.mixed_port),
+        ("redir-port", overrides.redir_port),
+        ("tproxy-port", overrides.tproxy_port),
+    ];
+    for (key, value) in ports {
+        if let Some(port) = value {
+            if running.get(key).and_then(|v| v.as_u64()) != Some(port as u64) {
+                restart_required.push(key.to_string());
+            }
+        }
+    }
+
+    // `/configs` does not expose the controller address, compare against our state instead.
+    if let Some(ref ec) = overrides.external_controller {
+        let current = (normalize_api_host(controller.0), controller.1);
+        if parse_external_controller(ec) != Some(current) {
+            restart_required.push("external-controller".to_string());
+        }
+    }
+
+    if let Some(allow_lan) = overrides.allow_lan {
+        if running.get("allow-lan").and_then(|v| v.as_bool()) != Some(allow_lan) {
+            patch.insert("allow-lan".to_string(), serde_json::Value::Bool(allow_lan));
+            applied.push("allow-lan".to_string());
+        }
+    }
+
+    if let Some(ref mode) = overrides.mode {
+        let current = running.get("mode").and_then(|v| v.as_str()).unwrap_or("");
+        if !current.eq_ignore_ascii_case(mode) {
+            patch.insert("mode".to_string(), serde_json::Value::String(mode.clone()));
+            applied.push("mode".to_string());
+        }
+    }
+
+    if let Some(ref log_level) = overrides.log_level {
+        let current = running.get("log-level").and_then(|v| v.as_str()).unwrap_or("");
+        if !current.eq_ignore_ascii_case(log_level) {
+            patch.insert("log-level".to_string(), serde_json::Value::String(log_level.clone()));
+            applied.push("log-level".to_string());
+        }
+    }
+
+    if let Some(ref tun) = overrides.tun {
+        let running_tun = running.get("tun").cloned().unwrap_or(serde_json::Value::Null);
+        let mut tun_patch = serde_json::Map::new();
+
+        // Enabling/disabling TUN needs a full config reload: the DNS module is only
+        // initialized at startup, and dns-hijack requires DNS to be listening.
+        if let Some(enable) = tun.enable {
+            if running_tun.get("enable").and_then(|v| v.as_bool()).unwrap_or(false) != enable {
+                restart_required.push("tun.enable".to_string());
+            }
+        }
+        if let Some(ref device_id) = tun.device_id {
+            if running_tun.get("device").and_then(|v| v.as_str()) != Some(device_id.as_str()) {
+                restart_required.push("tun.device-id".to_string());
+            }
+        }
+        // Routes are installed when the TUN device comes up, so exclusions need a reload
+        if let Some(ref exclusions) = tun.route_exclude_address {
+            let current: Vec<String> = running_tun
+                .get("route-exclude-address")
+                .and_then(|v| v.as_array())
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if &current != exclusions {
+                restart_required.push("tun.route-exclude-address".to_string());
+            }
+        }
+        if let Some(ref stack) = tun.stack {
+            let current = running_tun.get("stack").and_then(|v| v.as_str()).unwrap_or("");
+            if !current.eq_ignore_ascii_case(stack) {
+                tun_patch.insert("stack".to_string(), serde_json::Value::String(stack.clone()));
+            }
+        }
+        if let Some(mtu) = tun.mtu {
+            if running_tun.get("mtu").and_then(|v| v.as_u64()) != Some(mtu as u64) {
+                tun_patch.insert("mtu".to_string(), serde_json::Value::Number(mtu.into()));
+            }
+        }
+        let bool_fields = [
+            ("strict-route", tun.strict_route),
+            ("auto-route", tun.auto_route),
+            ("auto-detect-interface", tun.auto_detect_interface),
+        ];
+        for (key, value) in bool_fields {
+            if let Some(val) = value {
+                if running_tun.get(key).and_then(|v| v.as_bool()) != Some(val) {
+                    tun_patch.insert(key.to_string(), serde_json::Value::Bool(val));
+                }
+            }
+        }
+        if let Some(ref hijack) = tun.dns_hijack {
+            let current: Vec<String> = running_tun
+                .get("dns-hijack")
+                .and_then(|v| v.as_array())
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if &current != hijack {
+                tun_patch.insert(
+                    "dns-hijack".to_string(),
+                    serde_json::Value::Array(
+                        hijack.iter().cloned().map(serde_json::Value::String).collect(),
+                    ),
+                );
+            }
+        }
+
+        if !tun_patch.is_empty() {
+            applied.extend(tun_patch.keys().map(|k| format!("tun.{}", k)));
+            patch.insert("tun".to_string(), serde_json::Value::Object(tun_patch));
+        }
+    }
+
+    (patch, applied, restart_required)
+}
+
+/// Restart a running core so it picks up the current overrides.
+/// Unlike `restart_core`, this leaves the system proxy untouched.
+async fn restart_core_with_overrides(
+    app: &tauri::AppHandle,
+    state: State<'_, MihomoState>,
+) -> Result<(), String> {
+    stop_core_inner(state.inner()).await?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+    if let Err(e) = start_core_inner(state.clone(), None).await {
+        let _ = app.emit("core-started", CoreStartedEvent { success: false, message: Some(e.clone()) });
+        return Err(format!("Failed to restart core to apply overrides: {}", e));
+    }
+    let _ = app.emit("core-started", CoreStartedEvent { success: true, message: None });
+    rearm_kill_switch(state.inner()).await;
+
+    Ok(())
+}
+
+/// Config path of the active profile, as currently recorded in profiles.json
+fn active_profile_config_path() -> PathBuf {
+    crate::profiles::get_active_profile_path()
+        .ok()
+        .flatten()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| get_config_dir().join("config.yaml"))
+}
+
+/// Re-read the active profile from profiles.json and realign the core with it.
+/// Returns whether the running core was reloaded.
+pub async fn refresh_active_config_path_inner(
+    app: &tauri::AppHandle,
+    state: State<'_, MihomoState>,
+) -> Result<bool, String> {
+    let active = active_profile_config_path();
+    let previous = state.profile_path.lock().map_err(|e| e.to_string())?.clone();
+
+    if previous.as_ref() == Some(&active) {
+        return Ok(false);
+    }
+
+    tracing::info!("Active profile changed: {:?} -> {:?}", previous, active);
+
+    if is_core_running(state.inner()) {
+        // start_core_inner re-resolves the active profile and updates both paths
+        restart_core_with_overrides(app, state.clone()).await?;
+        return Ok(true);
+    }
+
+    *state.profile_path.lock().map_err(|e| e.to_string())? = Some(active.clone());
+    *state.config_path.lock().map_err(|e| e.to_string())? = Some(active);
+    Ok(false)
+}
+
+/// Point a stopped core at the active profile without restarting anything.
+/// A running core keeps its paths (they describe what it was started with);
+/// the caller's next restart re-resolves the active profile.
+pub fn sync_active_config_path(state: &MihomoState) -> Result<(), String> {
+    if is_core_running(state) {
+        return Ok(());
+    }
+
+    let active = active_profile_config_path();
+    *state.profile_path.lock().map_err(|e| e.to_string())? = Some(active.clone());
+    *state.config_path.lock().map_err(|e| e.to_string())? = Some(active);
+    Ok(())
+}
+
+/// Re-read the active profile path (e.g. after profiles were edited externally) and
+/// reload the core if it is running a different profile
+#[tauri::command]
+pub async fn refresh_active_config_path(
+    app: tauri::AppHandle,
+    state: State<'_, MihomoState>,
+) -> Result<bool, String> {
+    refresh_active_config_path_inner(&app, state).await
+}
+
+/// Apply the saved user overrides to the running core with as little disruption as possible.
+/// Reloadable keys are sent via PATCH /configs; the core is only restarted when a key that
+/// cannot be hot-reloaded (e.g. a listen port) changed.
+#[tauri::command]
+pub async fn apply_overrides_live(
+    app: tauri::AppHandle,
+    state: State<'_, MihomoState>,
+) -> Result<LiveApplyResult, String> {
+    if !is_core_running(state.inner()) {
+        tracing::info!("apply_overrides_live: Core is not running, overrides apply on next start");
+        return Ok(LiveApplyResult::default());
+    }
+
+    let overrides = crate::user_overrides::load_overrides();
+    let running = fetch_running_config(state.inner()).await?;
+    let (api_host, api_port, api_secret) = {
+        let host = state.api_host.lock().map_err(|e| e.to_string())?.clone();
+        let port = *state.api_port.lock().map_err(|e| e.to_string())?;
+        let secret = get_api_secret_from_state(state.inner());
+        (host, port, secret)
+    };
+
+    let (patch, applied, restart_required) =
+        diff_overrides_against_running(&overrides, &running, (&api_host, api_port));
+
+    tracing::info!(
+        "apply_overrides_live: reloadable={:?}, restart_required={:?}",
+        applied, restart_required
+    );
+
+    if !restart_required.is_empty() {
+        // A restart re-applies every override, so the PATCH would be redundant.
+        restart_core_with_overrides(&app, state.clone()).await?;
+
+        return Ok(LiveApplyResult {
+            applied,
+            restart_required,
+            restarted: true,
+        });
+    }
+
+    if !patch.is_empty() {
+        let url = format!("http://{}:{}/configs", api_host, api_port);
+        let client = reqwest::Client::new();
+        let request = add_auth_header(
+            client
+                .patch(&url)
+                .json(&serde_json::Value::Object(patch.clone()))
+                .timeout(std::time::Duration::from_secs(5)),
+            api_secret.as_deref()
+        );
+        let response = request.send().await
+            .map_err(|e| format!("Failed to apply overrides: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Failed to apply overrides: {}", response.status()));
+        }
+
+        if let Some(mode) = patch.get("mode").and_then(|v| v.as_str()) {
+            let _ = app.emit("proxy-mode-changed", ProxyModeChangedEvent { mode: mode.to_string() });
+        }
+    }
+
+    Ok(LiveApplyResult {
+        applied,
+        restart_required,
+        restarted: false,
+    })
+}
@@ -2,7 +2,7 @@
 
 #[cfg(target_os = "macos")]
 async fn stop_user_mode(state: &MihomoState) -> Result<(), String> {
-    println!("Stopping user mode...");
+    tracing::info!("Stopping user mode...");
     
     // Stop child process if running
     {
@@ -89,7 +89,7 @@ async fn stop_service_mode_silent(state: &MihomoState) -> Result<bool, String> {
         if let Ok(resp) = req.json(&payload).send().await {
             if resp.status().is_success() {
                 silent_success = true;
-                println!("Service mode stopped silently (idling on stop.yaml)");
+                tracing::info!("Service mode stopped silently (idling on stop.yaml)");
             }
         }
     }
@@ -99,10 +99,10 @@ async fn stop_service_mode_silent(state: &MihomoState) -> Result<bool, String> {
     } else {
         // Check if actually running. If it's already down/unresponsive, treat as success.
         if is_port_in_use(api_port) || is_pid_running(find_mihomo_pid_by_port(api_port).unwrap_or(0)) {
-            println!("Service mode silent stop failed and core still active.");
+            tracing::warn!("Service mode silent stop failed and core still active.");
             Ok(false)
         } else {
-            println!("Service mode appears already stopped or unresponsive.");
+            tracing::info!("Service mode appears already stopped or unresponsive.");
             Ok(true)
         }
     }
@@ -110,7 +110,7 @@ async fn stop_service_mode_silent(state: &MihomoState) -> Result<bool, String> {
 
 #[cfg(target_os = "macos")]
 async fn stop_service_mode(state: &MihomoState) -> Result<(), String> {
-    println!("Stopping service mode...");
+    tracing::info!("Stopping service mode...");
 
     let api_port = *state.api_port.lock().map_err(|e| e.to_string())?;
 
@@ -119,14 +119,14 @@ async fn stop_service_mode(state: &MihomoState) -> Result<(), String> {
     if !silent_success {
         // Fallback: Check if actually running before trying launchctl
         if is_port_in_use(api_port) || is_pid_running(find_mihomo_pid_by_port(api_port).unwrap_or(0)) {
-            println!("Silent stop failed and core still active, using launchctl bootout...");
+            tracing::warn!("Silent stop failed and core still active, using launchctl bootout...");
             // Try without sudo first - if the service was loaded by root, this may fail
             // but that's OK, the service will be stopped on next restart anyway
             let _ = Command::new("launchctl")
                 .args(["bootout", &format!("system/{}", SERVICE_LABEL)])
                 .output();
         } else {
-            println!("Service mode appears already stopped or unresponsive.");
+            tracing::info!("Service mode appears already stopped or unresponsive.");
         }
     }
 
@@ -145,7 +145,7 @@ pub async fn force_stop_service_mode_on_exit(state: &MihomoState) -> Result<(),
         return Ok(()); // Service mode not installed
     }
     
-    println!("App Exit: Service Mode cleanup (service will continue running)...");
+    tracing::info!("App Exit: Service Mode cleanup (service will continue running)...");
     
     // Check if TUN is enabled and disable it to restore DNS
     // This prevents DNS issues after app exit
@@ -160,9 +160,9 @@ pub async fn force_stop_service_mode_on_exit(state: &MihomoState) -> Result<(),
         .send()
         .await;
     
-    println!("App Exit: TUN disabled (if was enabled), DNS restored.");
-    println!("App Exit: Service Mode will continue running in background.");
-    println!("App Exit: Use Settings -> Uninstall Service Mode to fully stop the service.");
+    tracing::info!("App Exit: TUN disabled (if was enabled), DNS restored.");
+    tracing::info!("App Exit: Service Mode will continue running in background.");
+    tracing::info!("App Exit: Use Settings -> Uninstall Service Mode to fully stop the service.");
     
     Ok(())
 }
@@ -176,7 +176,7 @@ async fn ensure_user_mode_running(
     stop_service_mode(state.inner()).await?;
     
     // Then start user mode
-    println!("Starting user mode...");
+    tracing::info!("Starting user mode...");
     let options = StartOptions {
         config_path: Some(config_path.to_string_lossy().to_string()),
         external_controller: None,
@@ -199,12 +199,12 @@ async fn ensure_service_mode_running(
     
     // Ensure helper is installed
     if !is_privileged_helper_valid() {
-        println!("Service mode requested but helper not installed, installing...");
+        tracing::info!("Service mode requested but helper not installed, installing...");
         install_privileged_helper(app, state.clone()).await?;
     }
     
     // Start service mode
-    println!("Starting service mode...");
+    tracing::info!("Starting service mode...");
     let options = StartOptions {
         config_path: Some(config_path.to_string_lossy().to_string()),
         external_controller: None,
@@ -240,6 +240,8 @@ pub async fn start_core(
 
     // Emit success event
     let _ = app.emit("core-started", CoreStartedEvent { success: true, message: None });
+
+    rearm_kill_switch(state.inner()).await;
     
     Ok(res)
 }
@@ -258,37 +260,10 @@ async fn start_service_mode(
     let old_secret = parse_api_secret_from_file(&system_config);
 
     // 2. Apply user overrides to the config before writing to system path
-    let content = std::fs::read_to_string(&config_path)
-        .map_err(|e| format!("Failed to read config: {}", e))?;
-    
-    let final_content = match serde_yaml::from_str::<serde_yaml::Value>(&content) {
-        Ok(mut yaml) => {
-            let overrides = crate::user_overrides::load_overrides();
-            if let Err(e) = crate::user_overrides::apply_overrides_to_yaml(&mut yaml, &overrides) {
-                eprintln!("Warning: Failed to apply user overrides to Service Mode config: {}", e);
-                content.clone()
-            } else {
-                match serde_yaml::to_string(&yaml) {
-                    Ok(modified) => {
-                        println!("Service Mode: Applied user overrides to config");
-                        modified
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to serialize modified config: {}", e);
-                        content.clone()
-                    }
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("Failed to parse config YAML: {}", e);
-            content.clone()
-        }
-    };
-    
     // We expect the file to be writable by user (chown user:staff was done during install)
-    std::fs::write(&system_config, &final_content)
+    merge_config_with_overrides(&config_path, &system_config, "service")
         .map_err(|e| format!("Failed to write system config: {}", e))?;
+    tracing::info!("Service Mode: Applied user overrides to config");
 
     // 3. Parse NEW config to get what we expect (from system_config, not the original config_path!)
     let (new_host, new_port) = parse_external_controller_from_file(&system_config)
@@ -306,7 +281,7 @@ async fn start_service_mode(
         "path": SYSTEM_CONFIG_PATH
     });
 
-    println!("Service Mode: Reloading config via API at {}", reload_url);
+    tracing::info!("Service Mode: Reloading config via API at {}", reload_url);
     let resp = req.json(&payload).send().await;
 
     // If reload fails (e.g. service crashed, or port changed and we missed it), fallback to restart
@@ -315,16 +290,34 @@ async fn start_service_mode(
         if r.status().is_success() {
             reloaded = true;
         } else {
-            println!("Service Mode: API reload failed with status: {}", r.status());
+            tracing::warn!("Service Mode: API reload failed with status: {}", r.status());
         }
     } else {
-        println!("Service Mode: API request failed");
+        tracing::warn!("Service Mode: API request failed");
     }
 
+    // The guessed port may be stale; find where the controller really is and retry once
     if !reloaded {
-        println!("Service Mode: Attempting to restart service via launchctl...");
+        if let Ok(endpoint) = rediscover_controller_inner(state.inner()).await {
+            if endpoint.api_port != old_port {
+                let retry_url = format!(
+                    "http://{}:{}/configs?force=true",
+                    endpoint.api_host, endpoint.api_port
+                );
+                tracing::info!("Service Mode: Retrying reload via rediscovered controller at {}", retry_url);
+                let request = add_auth_header(client.put(&retry_url), old_secret.as_deref());
+                reloaded = matches!(
+                    request.json(&payload).send().await,
+                    Ok(r) if r.status().is_success()
+                );
+            }
+        }
+    }
+
+    if !reloaded {
+        tracing::info!("Service Mode: Attempting to restart service via launchctl...");
         if let Err(err) = enable_service_launchdaemon().await {
-            println!("Service Mode: Failed to restart service: {}", err);
+            tracing::warn!("Service Mode: Failed to restart service: {}", err);
         } else {
             tokio::time::sleep(tokio::time::Duration::from_millis(2000)).await;
         }
@@ -380,23 +373,16 @@ async fn start_core_inner(
     }
 
     // Determine config path first
-    let config_path = if let Some(ref opts) = options {
-        if let Some(ref path) = opts.config_path {
-            PathBuf::from(path)
-        } else {
-            match crate::profiles::get_active_profile_path() {
-                Ok(Some(path)) => PathBuf::from(path),
-                _ => get_config_dir().join("config.yaml"),
-            }
-        }
-    } else {
-        match crate::profiles::get_active_profile_path() {
-            Ok(Some(path)) => PathBuf::from(path),
-            _ => get_config_dir().join("config.yaml"),
-        }
-    };
+    let config_path = options
+        .as_ref()
+        .and_then(|opts| opts.config_path.as_ref())
+        .map(PathBuf::from)
+        .unwrap_or_else(active_profile_config_path);
 
-    println!("Starting core with config path: {:?}", config_path);
+    tracing::info!("Starting core with config path: {:?}", config_path);
+    if let Ok(mut profile_lock) = state.profile_path.lock() {
+        *profile_lock = Some(config_path.clone());
+    }
 
     // Apply User Overrides (Generic Logic)
     //
@@ -407,80 +393,23 @@ async fn start_core_inner(
     // We instead write a stable runtime config file under app config dir and reuse it across restarts.
     let actual_config_path = {
         let overrides = crate::user_overrides::load_overrides();
-        println!("Loaded user overrides: {:?}", overrides);
-
-        let overrides_empty = overrides.port.is_none()
-            && overrides.socks_port.is_none()
-            && overrides.mixed_port.is_none()
-            && overrides.redir_port.is_none()
-            && overrides.tproxy_port.is_none()
-            && overrides.allow_lan.is_none()
-            && overrides.external_controller.is_none()
-            && overrides
-                .tun
-                .as_ref()
-                .map(|tun| {
-                    tun.enable.is_none()
-                        && tun.stack.is_none()
-                        && tun.device_id.is_none()
-                        && tun.mtu.is_none()
-                        && tun.strict_route.is_none()
-                        && tun.auto_route.is_none()
-                        && tun.auto_detect_interface.is_none()
-                        && tun.dns_hijack.is_none()
-                })
-                .unwrap_or(true);
+        tracing::debug!("Loaded user overrides: {:?}", overrides);
+
+        // The base template is only merged into the runtime config, so it needs one too
+        let overrides_empty =
+            !overrides.has_effective_fields() && load_base_template().is_none();
 
         if overrides_empty {
             config_path.clone()
         } else {
-            match std::fs::read_to_string(&config_path) {
-                Ok(content) => match serde_yaml::from_str::<serde_yaml::Value>(&content) {
-                    Ok(mut yaml) => {
-                        if let Err(e) = crate::user_overrides::apply_overrides_to_yaml(&mut yaml, &overrides) {
-                            eprintln!("Warning: Failed to apply user overrides: {}", e);
-                            config_path.clone()
-                        } else {
-                            match serde_yaml::to_string(&yaml) {
-                                Ok(modified_content) => {
-                                    let runtime_dir = get_config_dir();
-                                    if let Err(e) = std::fs::create_dir_all(&runtime_dir) {
-                                        eprintln!(
-                                            "Failed to create runtime config dir {:?}: {}",
-                                            runtime_dir, e
-                                        );
-                                        config_path.clone()
-                                    } else {
-                                        let runtime_path = runtime_dir.join("config.runtime.yaml");
-                                        if let Err(e) = std::fs::write(&runtime_path, &modified_content) {
-                                            eprintln!(
-                                                "Failed to write runtime config {:?}: {}",
-                                                runtime_path, e
-                                            );
-                                            config_path.clone()
-                                        } else {
-                                            println!(
-                                                "Applied user overrides, using runtime config: {:?}",
-                                                runtime_path
-                                            );
-                                            runtime_path
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    eprintln!("Failed to serialize modified config: {}", e);
-                                    config_path.clone()
-                                }
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Failed to parse config YAML: {}", e);
-                        config_path.clone()
-                    }
-                },
+            let runtime_path = get_config_dir().join(RUNTIME_CONFIG_FILE);
+            match merge_config_with_overrides(&config_path, &runtime_path, "runtime") {
+                Ok(_) => {
+                    tracing::info!("Applied user overrides, using runtime config: {:?}", runtime_path);
+                    runtime_path
+                }
                 Err(e) => {
-                    eprintln!("Failed to read config file: {}", e);
+                    tracing::error!("Failed to write runtime config: {}", e);
                     config_path.clone()
                 }
             }
@@ -500,7 +429,7 @@ async fn start_core_inner(
             })
             .unwrap_or(CoreMode::User);
         
-        println!("Target mode: {:?}", target_mode);
+        tracing::info!("Target mode: {:?}", target_mode);
         
         // Check for transition lock
         if let Ok(mut pending) = state.pending_transition.lock() {
@@ -541,7 +470,7 @@ async fn start_core_inner(
                 let service_running = is_privileged_helper_loaded();
                 
                 if service_running {
-                    println!("Service Mode LaunchDaemon is loaded, attempting to stop...");
+                    tracing::info!("Service Mode LaunchDaemon is loaded, attempting to stop...");
                     
                     // Try to silently stop Service Mode first (no password prompt)
                     let silent_stop_result = stop_service_mode_silent(state.inner()).await;
@@ -549,7 +478,7 @@ async fn start_core_inner(
                     // If silent stop failed (Service Mode still running), we need to disable
                     // the LaunchDaemon to prevent dual-core scenario. This requires admin privileges.
                     if let Ok(false) = silent_stop_result {
-                        println!("Service Mode still active after silent stop, disabling LaunchDaemon...");
+                        tracing::info!("Service Mode still active after silent stop, disabling LaunchDaemon...");
                         if let Err(e) = disable_service_launchdaemon().await {
                             user_mode_block_error = Some(format!(
                                 "Service Mode is running and could not be disabled: {}. \
@@ -562,7 +491,7 @@ Please go to Settings and switch to User Mode, or manually stop the privileged h
                         }
                     }
                 } else {
-                    println!("Service Mode is not running, proceeding directly to User Mode startup");
+                    tracing::info!("Service Mode is not running, proceeding directly to User Mode startup");
                 }
                 
                 // Continue with user mode startup below
@@ -658,7 +587,7 @@ Please go to Settings and switch to User Mode, or manually stop the privileged h
     #[cfg(any(target_os = "macos", target_os = "linux"))]
     if explicit_root.is_none() {
         let tun_enabled = read_tun_from_config(&actual_config_path).unwrap_or(false);
-        println!("TUN enabled check: tun_enabled={}, actual_config_path={:?}", tun_enabled, actual_config_path);
+        tracing::info!("TUN enabled check: tun_enabled={}, actual_config_path={:?}", tun_enabled, actual_config_path);
         if tun_enabled {
             #[cfg(target_os = "macos")]
             {
@@ -808,7 +737,7 @@ pub async fn stop_core(app: tauri::AppHandle, state: State<'_, MihomoState>) ->
     let _ = set_system_proxy(app.clone(), false, None).await;
 
     let result = stop_core_inner(state.inner()).await;
-    
+
     // Emit stopped event
     let _ = app.emit("core-stopped", CoreStoppedEvent { success: result.is_ok() });
     
@@ -816,6 +745,12 @@ pub async fn stop_core(app: tauri::AppHandle, state: State<'_, MihomoState>) ->
 }
 
 pub async fn stop_core_inner(state: &MihomoState) -> Result<(), String> {
+    // An intentional stop (including app exit) shouldn't cut the network, only
+    // unexpected exits do. Disarm before the controller port goes away.
+    if let Err(e) = sync_kill_switch(state, false).await {
+        tracing::warn!("Failed to disarm kill-switch: {}", e);
+    }
+
     #[cfg(target_os = "macos")]
     {
         // If we are in Service Mode, do NOT attempt to kill arbitrary PIDs.
@@ -828,7 +763,7 @@ pub async fn stop_core_inner(state: &MihomoState) -> Result<(), String> {
             .unwrap_or(false);
 
         if is_service_mode {
-            println!("Stopping core in Service Mode...");
+            tracing::info!("Stopping core in Service Mode...");
             let _ = stop_service_mode(state).await;
 
             // Mark stopped to keep UI consistent
@@ -872,7 +807,7 @@ pub async fn stop_core_inner(state: &MihomoState) -> Result<(), String> {
                 // Never kill ourselves (guard against incorrect PID recovery).
                 let current_pid = std::process::id();
                 if pid == current_pid {
-                    println!("Refusing to kill current app PID {}", pid);
+                    tracing::warn!("Refusing to kill current app PID {}", pid);
                     return Ok(());
                 }
 
@@ -901,7 +836,7 @@ pub async fn stop_core_inner(state: &MihomoState) -> Result<(), String> {
         // If Service Mode is active (helper installed), we PREFER to stop it silently by reloading an empty config.
         // This avoids password prompt on every "Stop" or "App Exit".
         if is_privileged_helper_valid() {
-            println!("Service Mode: Attempting silent stop via API reload...");
+            tracing::info!("Service Mode: Attempting silent stop via API reload...");
             
             let api_port = *state.api_port.lock().map_err(|e| e.to_string())?;
             let api_secret = {
@@ -932,7 +867,7 @@ pub async fn stop_core_inner(state: &MihomoState) -> Result<(), String> {
                 
                 if let Ok(resp) = req.json(&payload).send().await {
                     if resp.status().is_success() {
-                        println!("Service Mode: Silent stop success (idling).");
+                        tracing::info!("Service Mode: Silent stop success (idling).");
                         silent_success = true;
                     }
                 }
@@ -946,13 +881,13 @@ pub async fn stop_core_inner(state: &MihomoState) -> Result<(), String> {
             } else {
                 // Fallback: Check if active before trying launchctl
                 if is_port_in_use(api_port) {
-                    println!("Service Mode: Silent stop failed, trying launchctl bootout without sudo...");
+                    tracing::warn!("Service Mode: Silent stop failed, trying launchctl bootout without sudo...");
                     // Try without sudo - if it fails, that's OK, service will be stopped next restart
                     let _ = Command::new("launchctl")
                         .args(["bootout", &format!("system/{}", SERVICE_LABEL)])
                         .output();
                 } else {
-                    println!("Service Mode: Silent stop failed but port is closed, assuming stopped.");
+                    tracing::warn!("Service Mode: Silent stop failed but port is closed, assuming stopped.");
                 }
             }
         }
@@ -975,7 +910,7 @@ pub async fn stop_core_inner(state: &MihomoState) -> Result<(), String> {
 }
 
 #[cfg(target_os = "macos")]
-const SERVICE_PLIST_PATH: &str = "/Library/LaunchDaemons/com.aqiu.service.plist";
+use crate::service::SERVICE_PLIST_PATH;
 #[cfg(target_os = "macos")]
 #[allow(dead_code)]
 const SYSTEM_DIR: &str = "/Library/Application Support/aqiu";
@@ -989,6 +924,47 @@ const STOP_CONFIG_PATH: &str = "/Library/Application Support/aqiu/stop.yaml";
 #[cfg(target_os = "macos")]
 const SERVICE_LABEL: &str = "com.aqiu.service";
 
+/// Check that the Service Mode config and its directory are writable by the current
+/// user before touching anything, so a TUN toggle fails up front instead of halfway.
+/// The install script hands both to the user; the error carries the command to redo that.
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub fn precheck_service_writable() -> Result<(), String> {
+    let config = std::path::Path::new(SYSTEM_CONFIG_PATH);
+    let dir = std::path::Path::new(SYSTEM_DIR);
+
+    let problem = if !dir.is_dir() {
+        Some(format!("{} does not exist", SYSTEM_DIR))
+    } else {
+        let test_path = dir.join(".write_test");
+        let dir_writable = std::fs::write(&test_path, "test").is_ok();
+        let _ = std::fs::remove_file(&test_path);
+
+        // Open for append without writing, so an existing config is left untouched
+        let config_writable = !config.exists()
+            || std::fs::OpenOptions::new().append(true).open(config).is_ok();
+
+        match (dir_writable, config_writable) {
+            (true, true) => None,
+            (false, _) => Some(format!("{} is not writable", SYSTEM_DIR)),
+            (true, false) => Some(format!("{} is not writable", SYSTEM_CONFIG_PATH)),
+        }
+    };
+
+    match problem {
+        None => Ok(()),
+        Some(problem) => {
+            let user = std::env::var("USER").unwrap_or_else(|_| "$USER".to_string());
+            let fix = format!(
+                "sudo chown {user}:staff \"{}\" \"{}\"",
+                SYSTEM_DIR, SYSTEM_CONFIG_PATH
+            );
+            tracing::warn!("Service config precheck failed: {}", problem);
+            Err(format!("{}. Fix it with: {} (or reinstall Service Mode)", problem, fix))
+        }
+    }
+}
+
 #[cfg(target_os = "macos")]
 const SERVICE_PLIST_CONTENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
 <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
@@ -1022,9 +998,12 @@ fn get_privileged_helper_path() -> PathBuf {
     PathBuf::from(SERVICE_PLIST_PATH) // Changed semantics: now checks plist existence
 }
 
+/// The helper is usable only if its plist exists and points at a runnable binary;
+/// a leftover plist from a failed install must not be treated as Service Mode
 #[cfg(target_os = "macos")]
 fn is_privileged_helper_valid() -> bool {
     std::path::Path::new(SERVICE_PLIST_PATH).exists()
+        && crate::service::check_plist_program().is_ok()
 }
 
 #[cfg(target_os = "macos")]
@@ -1046,7 +1025,8 @@ fn is_privileged_helper_loaded() -> bool {
 
 #[cfg(target_os = "macos")]
 async fn disable_service_launchdaemon() -> Result<(), String> {
-    if !is_privileged_helper_valid() {
+    // A broken install still needs to be booted out, so only check the plist here
+    if !std::path::Path::new(SERVICE_PLIST_PATH).exists() {
         return Ok(());
     }
 
@@ -1143,7 +1123,7 @@ pub async fn install_privileged_helper(
             .ok_or("Install script not found")?
     };
 
-    println!("Installing Service Mode with script: {:?}", install_script);
+    tracing::info!("Installing Service Mode with script: {:?}", install_script);
 
     // Use osascript with AppleScript to show native macOS authorization dialog
     // This is the ONLY place that requires admin password (one-time setup)
@@ -1174,18 +1154,24 @@ pub async fn install_privileged_helper(
     // Wait a moment for service to start
     tokio::time::sleep(tokio::time::Duration::from_millis(3000)).await;
 
+    // The plist must point at a binary that actually landed on disk
+    crate::service::check_plist_program()
+        .map_err(|e| format!("Service installed incorrectly: {}", e))?;
+
     // Verify service is running
     // 1. Check if responding on ANY common port (7890 or 9090)
-    if is_port_in_use(7890) || is_port_in_use(9090) || is_port_in_use(29090) {
-        return Ok(());
-    }
-    
     // 2. Double check with launchctl
-    if let Ok(true) = get_privileged_helper_status().await {
-        Ok(())
-    } else {
-        Err("Service installed but failed to start. Check logs at /Library/Application Support/aqiu/service.log".to_string())
+    let started = is_port_in_use(7890)
+        || is_port_in_use(9090)
+        || is_port_in_use(29090)
+        || matches!(get_privileged_helper_status().await, Ok(true));
+    if !started {
+        return Err("Service installed but failed to start. Check logs at /Library/Application Support/aqiu/service.log".to_string());
     }
+
+    // The core was stopped (and the kill-switch disarmed) above; it is back under the service
+    rearm_kill_switch(state.inner()).await;
+    Ok(())
 }
 
 #[cfg(target_os = "macos")]
@@ -1197,7 +1183,7 @@ pub async fn uninstall_privileged_helper(
     use tauri::Manager;
     use std::process::Command as StdCommand;
     
-    println!("Uninstalling privileged helper...");
+    tracing::info!("Uninstalling privileged helper...");
     
     // Check if core was running before uninstall
     let was_running = is_core_running(state.inner());
@@ -1230,7 +1216,7 @@ pub async fn uninstall_privileged_helper(
             .ok_or("Uninstall script not found")?
     };
 
-    println!("Uninstalling Service Mode with script: {:?}", uninstall_script);
+    tracing::info!("Uninstalling Service Mode with script: {:?}", uninstall_script);
 
     // Use osascript with AppleScript to show native macOS authorization dialog
     // This is the ONLY place that requires admin password for uninstall
@@ -1256,7 +1242,7 @@ pub async fn uninstall_privileged_helper(
         return Err(format!("Uninstallation failed: {}", stderr));
     }
     
-    println!("Privileged helper uninstalled successfully");
+    tracing::info!("Privileged helper uninstalled successfully");
     
     // Update desired mode to User
     if let Ok(mut desired) = state.desired_mode.lock() {
@@ -1265,7 +1251,7 @@ pub async fn uninstall_privileged_helper(
     
     // If core was running, automatically switch to user mode
     if was_running {
-        println!("Core was running, switching to user mode...");
+        tracing::info!("Core was running, switching to user mode...");
         
         let config_to_use = active_config
             .or_else(|| {
@@ -1278,10 +1264,10 @@ pub async fn uninstall_privileged_helper(
         
         match ensure_user_mode_running(state.clone(), config_to_use).await {
             Ok(_) => {
-                println!("Successfully switched to user mode");
+                tracing::info!("Successfully switched to user mode");
             }
             Err(e) => {
-                eprintln!("Failed to start user mode after uninstall: {}", e);
+                tracing::error!("Failed to start user mode after uninstall: {}", e);
                 // Don't fail the uninstall, just log the error
             }
         }
@@ -1313,13 +1299,13 @@ pub async fn restart_core(app: tauri::AppHandle, state: State<'_, MihomoState>)
 /// This handles the case where the app crashed but mihomo core is still running.
 #[tauri::command]
 pub async fn recover_orphaned_core(state: State<'_, MihomoState>) -> Result<bool, String> {
-    println!("Checking for orphaned core process...");
+    tracing::info!("Checking for orphaned core process...");
     
     let api_port = *state.api_port.lock().map_err(|e| e.to_string())?;
     
     // Check if something is listening on the API port
     if !is_port_in_use(api_port) {
-        println!("No process listening on port {}, no recovery needed", api_port);
+        tracing::info!("No process listening on port {}, no recovery needed", api_port);
         return Ok(false);
     }
     
@@ -1330,30 +1316,30 @@ pub async fn recover_orphaned_core(state: State<'_, MihomoState>) -> Result<bool
     let orphan_pid: Option<u32> = None;
     
     if orphan_pid.is_none() {
-        println!("Port {} is in use but couldn't identify the process", api_port);
+        tracing::info!("Port {} is in use but couldn't identify the process", api_port);
         return Ok(false);
     }
     
     let pid = orphan_pid.unwrap();
-    println!("Found orphaned core process with PID {} on port {}", pid, api_port);
+    tracing::info!("Found orphaned core process with PID {} on port {}", pid, api_port);
     
     // Try to verify it's actually mihomo by calling the API
     let api_host = state.api_host.lock().map_err(|e| e.to_string())?.clone();
     let version = get_version_from_api(&api_host, api_port).await;
     
     if version.is_err() {
-        println!("Process on port {} is not responding to mihomo API, not recovering", api_port);
+        tracing::warn!("Process on port {} is not responding to mihomo API, not recovering", api_port);
         return Ok(false);
     }
     
-    println!("Verified orphaned process is mihomo (version: {:?}), recovering state...", version);
+    tracing::info!("Verified orphaned process is mihomo (version: {:?}), recovering state...", version);
     
     // Update state to reflect the running core
     #[cfg(target_os = "macos")]
     {
         // Check if it's Service Mode (LaunchDaemon) or User Mode
         if is_privileged_helper_loaded() {
-            println!("Detected Service Mode LaunchDaemon, updating state...");
+            tracing::info!("Detected Service Mode LaunchDaemon, updating state...");
             if let Ok(mut mode) = state.current_mode.lock() {
                 *mode = CoreMode::Service;
             }
@@ -1361,7 +1347,7 @@ pub async fn recover_orphaned_core(state: State<'_, MihomoState>) -> Result<bool
                 *desired = CoreMode::Service;
             }
         } else {
-            println!("Detected User Mode orphaned process, caching PID...");
+            tracing::info!("Detected User Mode orphaned process, caching PID...");
             if let Ok(mut pid_lock) = state.root_pid.lock() {
                 *pid_lock = Some(pid);
             }
@@ -1387,7 +1373,7 @@ pub async fn recover_orphaned_core(state: State<'_, MihomoState>) -> Result<bool
         }
     }
     
-    println!("Successfully recovered orphaned core process (PID {})", pid);
+    tracing::info!("Successfully recovered orphaned core process (PID {})", pid);
     Ok(true)
 }
 
@@ -1647,7 +1633,7 @@ pub async fn download_core(
     use std::io::Write;
     use tauri::Emitter;
 
-    let target_dir = get_mihomo_path()
+    let target_dir = get_default_mihomo_path()
         .parent()
         .ok_or("Invalid Mihomo binary path (no parent directory)")?
         .to_path_buf();
@@ -1763,7 +1749,7 @@ pub async fn download_core(
             let name = file.name();
 
             if name.ends_with(".exe") {
-                let mut out_file = std::fs::File::create(get_mihomo_path())
+                let mut out_file = std::fs::File::create(get_default_mihomo_path())
                     .map_err(|e| format!("Failed to create file: {}", e))?;
                 std::io::copy(&mut file, &mut out_file)
                     .map_err(|e| format!("Failed to write file: {}", e))?;
@@ -1782,7 +1768,7 @@ pub async fn download_core(
             .read_to_end(&mut output_buffer)
             .map_err(|e| format!("Failed to decompress: {}", e))?;
 
-        let target_path = get_mihomo_path();
+        let target_path = get_default_mihomo_path();
         let mut out_file = std::fs::File::create(&target_path)
             .map_err(|e| format!("Failed to create file: {}", e))?;
         out_file
@@ -1813,7 +1799,7 @@ pub fn import_core_binary(path: String) -> Result<String, String> {
         return Err("Selected file does not exist".to_string());
     }
 
-    let target_path = get_mihomo_path();
+    let target_path = get_default_mihomo_path();
     if let Some(parent) = target_path.parent() {
         std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
@@ -1843,58 +1829,150 @@ pub async fn check_core_exists() -> Result<bool, String> {
         .map_err(|e| e.to_string())
 }
 
-/// Download GeoIP and GeoSite database files using mihomo official API
+/// Read the version string reported by `<binary> -v`
+/// (e.g. "Mihomo Meta v1.19.0 darwin arm64 ..." -> "v1.19.0")
+async fn get_core_binary_version(binary: &std::path::Path) -> Option<String> {
+    let mut cmd = tokio::process::Command::new(binary);
+    cmd.arg("-v").kill_on_drop(true);
+    let output = tokio::time::timeout(std::time::Duration::from_secs(5), cmd.output())
+        .await
+        .ok()?
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first_line = stdout.lines().next()?.trim();
+    first_line
+        .split_whitespace()
+        .find(|token| token.starts_with('v') && token[1..].starts_with(|c: char| c.is_ascii_digit()))
+        .or_else(|| first_line.split_whitespace().nth(2))
+        .map(|s| s.to_string())
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigCompatibility {
+    pub compatible: bool,
+    pub core_version: Option<String>,
+    /// "core_missing" | "core_too_old" | "config_error" (None when compatible)
+    pub issue: Option<String>,
+    pub message: Option<String>,
+    pub suggestion: Option<String>,
+}
+
+/// Phrases Mihomo emits when a config uses fields/types newer than the binary understands
+const NEWER_SCHEMA_MARKERS: [&str; 6] = [
+    "unsupport",
+    "not support",
+    "unknown field",
+    "unknown type",
+    "unknown network",
+    "invalid type",
+];
+
+fn classify_config_test_failure(output: &str) -> &'static str {
+    let lower = output.to_lowercase();
+    if NEWER_SCHEMA_MARKERS.iter().any(|m| lower.contains(m)) {
+        "core_too_old"
+    } else {
+        "config_error"
+    }
+}
+
+/// Validate a profile against the installed core (`mihomo -t`) and classify failures
+/// as "core too old" vs a genuine config error. `set_active_profile` runs it before activating.
 #[tauri::command]
-pub async fn download_geodata(
-    window: tauri::WebviewWindow,
-    state: tauri::State<'_, MihomoState>,
-) -> Result<String, String> {
-    use tauri::Emitter;
+pub async fn check_config_compatibility(id: String) -> Result<ConfigCompatibility, String> {
+    let profile_path = PathBuf::from(crate::profiles::get_profile_path(&id)?);
 
-    // Check if core is running
-    if !is_core_running(state.inner()) {
-        return Err("Core is not running. Please start the core first to update GEO database.".to_string());
+    let mihomo_path = get_mihomo_path();
+    if !mihomo_path.exists() {
+        return Ok(ConfigCompatibility {
+            compatible: false,
+            core_version: None,
+            issue: Some("core_missing".to_string()),
+            message: Some(format!("Mihomo binary not found at {:?}", mihomo_path)),
+            suggestion: Some("Download the core from Settings first.".to_string()),
+        });
     }
 
-    let _ = window.emit("download-progress", "Updating GEO database via mihomo API...");
+    let core_version = get_core_binary_version(&mihomo_path).await;
 
-    // Get API credentials
-    let (api_host, api_port, api_secret) = {
-        let host = state.api_host.lock().map_err(|e| e.to_string())?.clone();
-        let port = *state.api_port.lock().map_err(|e| e.to_string())?;
-        let secret = get_api_secret_from_state(state.inner());
-        (host, port, secret)
-    };
+    let mut cmd = tokio::process::Command::new(&mihomo_path);
+    cmd.arg("-t")
+        .arg("-d")
+        .arg(get_config_dir())
+        .arg("-f")
+        .arg(&profile_path)
+        .kill_on_drop(true);
 
-    // Use mihomo's official /upgrade/geo API
-    let url = format!("http://{}:{}/upgrade/geo", api_host, api_port);
-    
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(300))
-        .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let output = tokio::time::timeout(std::time::Duration::from_secs(30), cmd.output())
+        .await
+        .map_err(|_| "Config test timed out".to_string())?
+        .map_err(|e| format!("Failed to run config test: {}", e))?;
 
-    let mut req = client.post(&url);
-    
-    // Add authorization if secret is set
-    if let Some(s) = &api_secret {
-        req = req.header("Authorization", format!("Bearer {}", s));
-    }
-    
-    // Send empty JSON body as required by the API
-    req = req.json(&serde_json::json!({}));
-    
-    println!("Updating GEO database via API: {}", url);
-    
-    let response = req.send().await
-        .map_err(|e| format!("Failed to send GEO update request: {}", e))?;
+    let combined = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
-        return Err(format!("GEO update failed: {} - {}", status, error_text));
+    if output.status.success() && !combined.contains("test failed") {
+        return Ok(ConfigCompatibility {
+            compatible: true,
+            core_version,
+            issue: None,
+            message: None,
+            suggestion: None,
+        });
     }
 
+    // Keep only the error lines; the rest is startup noise
+    let message = combined
+        .lines()
+        .filter(|l| {
+            let lower = l.to_lowercase();
+            lower.contains("error") || lower.contains("fatal") || lower.contains("failed")
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let message = if message.is_empty() { combined.trim().to_string() } else { message };
+
+    let issue = classify_config_test_failure(&message);
+    tracing::warn!(
+        "check_config_compatibility: profile {} failed ({}) with core {:?}",
+        id, issue, core_version
+    );
+
+    let suggestion = if issue == "core_too_old" {
+        format!(
+            "This profile uses features the installed core ({}) doesn't support. Update the core via Download Core.",
+            core_version.as_deref().unwrap_or("unknown version")
+        )
+    } else {
+        "The profile itself is invalid. Check the reported error or update the subscription.".to_string()
+    };
+
+    Ok(ConfigCompatibility {
+        compatible: false,
+        core_version,
+        issue: Some(issue.to_string()),
+        message: Some(message),
+        suggestion: Some(suggestion),
+    })
+}
+
+/// Download GeoIP and GeoSite database files using mihomo official API,
+/// falling back to a direct download when the core is not running
+#[tauri::command]
+pub async fn download_geodata(
+    window: tauri::WebviewWindow,
+    state: tauri::State<'_, MihomoState>,
+) -> Result<String, String> {
+    use tauri::Emitter;
+
+    let _ = window.emit("download-progress", "Updating GEO database...");
+
+    let message = update_geodata_inner(state.inner()).await?;
+
     let _ = window.emit("download-progress", "GEO database updated successfully");
     
     
@@ -1904,7 +1982,7 @@ pub async fn download_geodata(
         let _ = window.emit("download-progress", "");
     });
     
-    Ok("GEO database updated successfully via mihomo API".to_string())
+    Ok(message)
 }
 
 /// Get paths for the application
@@ -1923,7 +2001,7 @@ pub fn get_app_paths() -> Result<serde_json::Value, String> {
 /// Download a profile/config from URL (deprecated, use profiles::update_profile_from_url instead)
 /// This function is kept for backward compatibility but now delegates to the profiles system
 #[tauri::command]
-pub async fn download_profile(url: String) -> Result<String, String> {
+pub async fn download_profile(app: tauri::AppHandle, url: String) -> Result<String, String> {
     use crate::profiles;
     
     // Create a new profile with the URL
@@ -1933,8 +2011,9 @@ pub async fn download_profile(url: String) -> Result<String, String> {
     // Update the profile from URL
     profiles::update_profile_from_url(profile.id.clone()).await?;
     
-    // Set it as active
-    profiles::set_active_profile(profile.id.clone())?;
+    // Set it as active; nothing restarts the core after this one, so realign here
+    profiles::set_active_profile(app.clone(), profile.id.clone()).await?;
+    profiles::realign_core_with_active_profile(&app).await?;
     
     Ok(profile.file_path)
 }
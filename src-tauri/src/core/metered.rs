@@ -0,0 +1,151 @@
+// ========== Metered Connections ==========
+
+/// Manual pause set by `pause_auto_updates`
+static AUTO_UPDATES_PAUSED_UNTIL: Mutex<Option<chrono::DateTime<chrono::Local>>> = Mutex::new(None);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AutoUpdatePauseStatus {
+    pub pause_updates_on_metered: bool,
+    /// None when the OS doesn't tell
+    pub metered: Option<bool>,
+    pub paused_until: Option<String>,
+    /// Why scheduled updates would be deferred right now, if they would
+    pub deferred_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AutoUpdateDeferredEvent {
+    /// What was deferred, e.g. "geodata"
+    pub kind: String,
+    pub reason: String,
+}
+
+/// NetworkManager's global `Metered` property: 1 = yes, 3 = guess-yes, 2/4 = no, 0 = unknown
+#[cfg(target_os = "linux")]
+fn is_metered_connection() -> Option<bool> {
+    let output = Command::new("busctl")
+        .args([
+            "get-property",
+            "org.freedesktop.NetworkManager",
+            "/org/freedesktop/NetworkManager",
+            "org.freedesktop.NetworkManager",
+            "Metered",
+        ])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Output looks like "u 4"
+    match String::from_utf8_lossy(&output.stdout).split_whitespace().nth(1)? {
+        "1" | "3" => Some(true),
+        "2" | "4" => Some(false),
+        _ => None,
+    }
+}
+
+/// Cost of the internet connection profile; anything but "Unrestricted" or roaming counts
+#[cfg(target_os = "windows")]
+fn is_metered_connection() -> Option<bool> {
+    use std::process::Command;
+
+    let script = "$p = [Windows.Networking.Connectivity.NetworkInformation,Windows.Networking.Connectivity,ContentType=WindowsRuntime]::GetInternetConnectionProfile(); \
+                  if ($p) { $c = $p.GetConnectionCost(); \"$($c.NetworkCostType)|$($c.Roaming)\" }";
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", script])
+        .output()
+        .ok()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (cost_type, roaming) = stdout.trim().split_once('|')?;
+    match cost_type {
+        "Unrestricted" => Some(roaming.eq_ignore_ascii_case("true")),
+        "Fixed" | "Variable" => Some(true),
+        _ => None,
+    }
+}
+
+/// macOS has no command-line view of Low Data Mode / expensive interfaces
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn is_metered_connection() -> Option<bool> {
+    None
+}
+
+fn auto_updates_paused_until() -> Option<chrono::DateTime<chrono::Local>> {
+    let mut paused = AUTO_UPDATES_PAUSED_UNTIL.lock().ok()?;
+    match *paused {
+        Some(until) if until > chrono::Local::now() => Some(until),
+        Some(_) => {
+            *paused = None;
+            None
+        }
+        None => None,
+    }
+}
+
+/// Why scheduled updates should wait right now, if they should
+fn auto_update_deferral_reason() -> Option<String> {
+    if let Some(until) = auto_updates_paused_until() {
+        return Some(format!("Auto-updates paused until {}", until.format("%H:%M")));
+    }
+
+    let pause_on_metered = crate::user_overrides::load_overrides()
+        .pause_updates_on_metered
+        .unwrap_or(false);
+    if pause_on_metered && is_metered_connection() == Some(true) {
+        return Some("Metered connection".to_string());
+    }
+
+    None
+}
+
+/// Check whether a scheduled update of `kind` may run now; emits `auto-update-deferred` if not
+fn defer_auto_update(app: &tauri::AppHandle, kind: &str) -> bool {
+    let Some(reason) = auto_update_deferral_reason() else {
+        return false;
+    };
+
+    tracing::info!("Deferring {} auto-update: {}", kind, reason);
+    let _ = app.emit(
+        "auto-update-deferred",
+        AutoUpdateDeferredEvent {
+            kind: kind.to_string(),
+            reason,
+        },
+    );
+    true
+}
+
+/// Get the metered-connection preference and whether updates are currently deferred
+#[tauri::command]
+pub fn get_auto_update_pause_status() -> Result<AutoUpdatePauseStatus, String> {
+    Ok(AutoUpdatePauseStatus {
+        pause_updates_on_metered: crate::user_overrides::load_overrides()
+            .pause_updates_on_metered
+            .unwrap_or(false),
+        metered: is_metered_connection(),
+        paused_until: auto_updates_paused_until().map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()),
+        deferred_reason: auto_update_deferral_reason(),
+    })
+}
+
+/// Defer scheduled updates while the OS reports a metered connection
+#[tauri::command]
+pub fn set_pause_updates_on_metered(enable: bool) -> Result<AutoUpdatePauseStatus, String> {
+    crate::user_overrides::persist_pause_updates_on_metered(enable)?;
+    get_auto_update_pause_status()
+}
+
+/// Pause scheduled updates for `minutes` (0 resumes them). Fallback for systems
+/// where the metered state can't be detected.
+#[tauri::command]
+pub fn pause_auto_updates(minutes: u32) -> Result<AutoUpdatePauseStatus, String> {
+    {
+        let mut paused = AUTO_UPDATES_PAUSED_UNTIL.lock().map_err(|e| e.to_string())?;
+        *paused = (minutes > 0)
+            .then(|| chrono::Local::now() + chrono::Duration::minutes(i64::from(minutes)));
+    }
+    tracing::info!("Auto-updates paused for {} minutes", minutes);
+    get_auto_update_pause_status()
+}
@@ -6,3 +6,13 @@ include!("macos_and_lifecycle.rs");
 include!("windows.rs");
 include!("tun.rs");
 include!("proxy_and_mode.rs");
+include!("live_config.rs");
+include!("geodata.rs");
+include!("dns.rs");
+include!("kill_switch.rs");
+include!("backup.rs");
+include!("leak_test.rs");
+include!("base_template.rs");
+include!("schedule.rs");
+include!("metered.rs");
+include!("cores.rs");
@@ -28,6 +28,10 @@ pub async fn set_mode(app: tauri::AppHandle, state: tauri::State<'_, MihomoState
         return Err(format!("Failed to set mode: {}", response.status()));
     }
 
+    if let Err(e) = crate::user_overrides::persist_mode_if_overridden(&mode) {
+        tracing::warn!("Failed to persist mode override: {}", e);
+    }
+
     // Emit event after successful mode change
     let _ = app.emit("proxy-mode-changed", ProxyModeChangedEvent { mode: mode.clone() });
 
@@ -151,9 +155,9 @@ pub async fn set_core_mode(
     // Switching *to service mode* should NOT disable TUN — users often switch modes
     // specifically to enable TUN without prompts.
     if matches!(target_mode, CoreMode::User) {
-        println!("Disabling TUN mode before switching to {:?} mode", target_mode);
+        tracing::info!("Disabling TUN mode before switching to {:?} mode", target_mode);
         if let Err(e) = crate::user_overrides::persist_tun_override(false) {
-            eprintln!("Warning: Failed to disable TUN during mode switch: {}", e);
+            tracing::warn!("Warning: Failed to disable TUN during mode switch: {}", e);
             // Continue anyway, this is not critical
         }
     }
@@ -162,12 +166,12 @@ pub async fn set_core_mode(
         CoreMode::User => {
             // Only disable if LaunchDaemon is actually loaded (避免不必要的密码提示)
             if is_privileged_helper_loaded() {
-                println!("Service Mode LaunchDaemon is loaded, disabling it...");
+                tracing::info!("Service Mode LaunchDaemon is loaded, disabling it...");
                 disable_service_launchdaemon()
                     .await
                     .map_err(|e| format!("Failed to disable Service Mode: {}", e))?;
             } else {
-                println!("Service Mode LaunchDaemon is not loaded, no need to disable");
+                tracing::info!("Service Mode LaunchDaemon is not loaded, no need to disable");
             }
         }
         CoreMode::Service => {
@@ -177,7 +181,7 @@ pub async fn set_core_mode(
                     .await
                     .map_err(|e| format!("Failed to enable Service Mode: {}", e))?;
             } else {
-                println!("Service Mode LaunchDaemon is already loaded, skipping enable step");
+                tracing::info!("Service Mode LaunchDaemon is already loaded, skipping enable step");
             }
         }
     }
@@ -190,25 +194,16 @@ pub async fn set_core_mode(
     
     // Persist mode preference for next app launch
     if let Err(e) = crate::user_overrides::persist_core_mode(&mode) {
-        eprintln!("Warning: Failed to persist core mode preference: {}", e);
+        tracing::warn!("Warning: Failed to persist core mode preference: {}", e);
     }
     
     // If core is running, restart with new mode
     if is_core_running(state.inner()) {
-        println!("Core is running, restarting with new mode: {:?}", target_mode);
+        tracing::info!("Core is running, restarting with new mode: {:?}", target_mode);
         
-        let config_path = {
-            state.config_path.lock()
-                .ok()
-                .and_then(|lock| lock.clone())
-                .or_else(|| {
-                    crate::profiles::get_active_profile_path()
-                        .ok()
-                        .flatten()
-                        .map(PathBuf::from)
-                })
-                .unwrap_or_else(|| get_config_dir().join("config.yaml"))
-        };
+        // Re-read the active profile: `config_path` holds the generated runtime/system
+        // config, and reloading that would skip any profile change since the last start
+        let config_path = active_profile_config_path();
         
         // Stop current mode
         stop_core_inner(state.inner()).await?;
@@ -224,15 +219,12 @@ pub async fn set_core_mode(
         };
         
         start_core_inner(state.clone(), Some(options)).await?;
+        rearm_kill_switch(state.inner()).await;
     } else {
         // Core is not running, auto-start with the new mode
-        println!("Core is not running; auto-starting in {:?} mode...", target_mode);
+        tracing::info!("Core is not running; auto-starting in {:?} mode...", target_mode);
 
-        let config_path = crate::profiles::get_active_profile_path()
-            .ok()
-            .flatten()
-            .map(PathBuf::from)
-            .unwrap_or_else(|| get_config_dir().join("config.yaml"));
+        let config_path = active_profile_config_path();
 
         let options = StartOptions {
             config_path: Some(config_path.to_string_lossy().to_string()),
@@ -242,6 +234,7 @@ pub async fn set_core_mode(
         };
 
         start_core_inner(state.clone(), Some(options)).await?;
+        rearm_kill_switch(state.inner()).await;
 
         // Notify frontend so Dashboard "Start" flips to "Stop" immediately.
         let _ = app.emit(
@@ -0,0 +1,177 @@
+// ========== Core Schedule ==========
+
+const SCHEDULE_FILE: &str = "schedule.json";
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScheduleEntry {
+    /// "start" or "stop"
+    pub action: String,
+    /// Local time, "HH:MM"
+    pub time: String,
+    /// Weekdays ("mon".."sun"); empty means every day
+    #[serde(default)]
+    pub days: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CoreScheduleEvent {
+    pub action: String,
+    pub time: String,
+    /// Set when the action was not performed
+    pub skipped: Option<String>,
+    pub error: Option<String>,
+}
+
+fn get_schedule_path() -> PathBuf {
+    get_config_dir().join(SCHEDULE_FILE)
+}
+
+fn load_schedule() -> Vec<ScheduleEntry> {
+    std::fs::read_to_string(get_schedule_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn normalize_schedule_entry(entry: ScheduleEntry) -> Result<ScheduleEntry, String> {
+    let action = entry.action.trim().to_lowercase();
+    if action != "start" && action != "stop" {
+        return Err(format!("Invalid schedule action: {} (expected start or stop)", entry.action));
+    }
+
+    let time = chrono::NaiveTime::parse_from_str(entry.time.trim(), "%H:%M")
+        .map_err(|_| format!("Invalid schedule time: {} (expected HH:MM)", entry.time))?
+        .format("%H:%M")
+        .to_string();
+
+    let mut days = Vec::new();
+    for day in &entry.days {
+        let short: String = day.trim().to_lowercase().chars().take(3).collect();
+        if !WEEKDAYS.contains(&short.as_str()) {
+            return Err(format!("Invalid schedule day: {}", day));
+        }
+        if !days.contains(&short) {
+            days.push(short);
+        }
+    }
+
+    Ok(ScheduleEntry { action, time, days })
+}
+
+/// Get the scheduled core start/stop actions
+#[tauri::command]
+pub fn get_core_schedule() -> Result<Vec<ScheduleEntry>, String> {
+    Ok(load_schedule())
+}
+
+/// Replace the scheduled core start/stop actions (an empty list disables the schedule)
+#[tauri::command]
+pub fn set_core_schedule(entries: Vec<ScheduleEntry>) -> Result<Vec<ScheduleEntry>, String> {
+    let entries = entries
+        .into_iter()
+        .map(normalize_schedule_entry)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let path = get_schedule_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let content = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to save schedule: {}", e))?;
+
+    tracing::info!("Core schedule saved with {} entries", entries.len());
+    Ok(entries)
+}
+
+/// Background task: fire scheduled start/stop actions once per matching minute.
+/// A scheduled start doesn't undo a stop the user made by hand, and actions that
+/// wouldn't change anything are skipped. A scheduled start restores the system proxy
+/// the scheduled stop turned off.
+pub async fn run_core_schedule(app: tauri::AppHandle) {
+    use chrono::Datelike;
+    use tauri::Manager;
+
+    let mut last_fired_minute = String::new();
+    // Whether the core is down because of a scheduled stop (as opposed to a manual one)
+    let mut stopped_by_schedule = false;
+    // Whether the system proxy was on when the scheduled stop turned it off
+    let mut restore_system_proxy = false;
+
+    loop {
+        tokio::time::sleep(tokio::time::Duration::from_secs(20)).await;
+
+        // Once the core is back up by any means, a later manual stop is the user's call
+        if stopped_by_schedule && is_core_running(app.state::<MihomoState>().inner()) {
+            stopped_by_schedule = false;
+            restore_system_proxy = false;
+        }
+
+        let now = chrono::Local::now();
+        let minute = now.format("%Y-%m-%d %H:%M").to_string();
+        if minute == last_fired_minute {
+            continue;
+        }
+
+        let time = now.format("%H:%M").to_string();
+        let weekday = WEEKDAYS[now.weekday().num_days_from_monday() as usize];
+        let Some(entry) = load_schedule().into_iter().find(|entry| {
+            entry.time == time && (entry.days.is_empty() || entry.days.iter().any(|d| d == weekday))
+        }) else {
+            continue;
+        };
+        last_fired_minute = minute;
+
+        let state = app.state::<MihomoState>();
+        let running = is_core_running(state.inner());
+        let manually_stopped = state.manually_stopped.lock().map(|s| *s).unwrap_or(false);
+
+        let mut event = CoreScheduleEvent {
+            action: entry.action.clone(),
+            time: entry.time.clone(),
+            skipped: None,
+            error: None,
+        };
+
+        match entry.action.as_str() {
+            "start" if running => event.skipped = Some("Core is already running".to_string()),
+            "start" if manually_stopped && !stopped_by_schedule => {
+                event.skipped = Some("Core was stopped manually".to_string())
+            }
+            "start" => match start_core(app.clone(), state.clone(), None).await {
+                Ok(_) => {
+                    if restore_system_proxy {
+                        if let Err(e) = set_system_proxy(app.clone(), true, None).await {
+                            tracing::warn!("Core schedule: failed to restore system proxy: {}", e);
+                        }
+                    }
+                    stopped_by_schedule = false;
+                    restore_system_proxy = false;
+                }
+                Err(e) => event.error = Some(e),
+            },
+            "stop" if !running => event.skipped = Some("Core is not running".to_string()),
+            "stop" => {
+                // stop_core turns the system proxy off; remember it for the next start
+                let proxy_enabled = get_system_proxy_status().unwrap_or(false);
+                match stop_core(app.clone(), state.clone()).await {
+                    Ok(()) => {
+                        stopped_by_schedule = true;
+                        restore_system_proxy = proxy_enabled;
+                    }
+                    Err(e) => event.error = Some(e),
+                }
+            }
+            _ => continue,
+        }
+
+        tracing::info!(
+            "Core schedule: {} at {} (skipped: {:?}, error: {:?})",
+            event.action,
+            event.time,
+            event.skipped,
+            event.error
+        );
+        let _ = app.emit("core-schedule-fired", event);
+    }
+}
@@ -68,7 +68,7 @@ async fn set_system_dns(app: &tauri::AppHandle, dns_server: &str) {
     let script_path = match find_script(app, "set_dns.sh") {
         Some(p) => p,
         None => {
-            println!("DNS setup: set_dns.sh not found in any search path");
+            tracing::warn!("DNS setup: set_dns.sh not found in any search path");
             return;
         }
     };
@@ -81,7 +81,7 @@ async fn set_system_dns(app: &tauri::AppHandle, dns_server: &str) {
         .canonicalize()
         .unwrap_or_else(|_| script_path.clone());
 
-    println!(
+    tracing::info!(
         "DNS setup: Setting system DNS to {} using {:?}",
         dns_server, script_abs
     );
@@ -97,15 +97,15 @@ async fn set_system_dns(app: &tauri::AppHandle, dns_server: &str) {
     match output {
         Ok(output) => {
             if output.status.success() {
-                println!("DNS setup: Successfully set system DNS to {}", dns_server);
+                tracing::info!("DNS setup: Successfully set system DNS to {}", dns_server);
             } else {
                 let stderr = String::from_utf8_lossy(&output.stderr);
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                println!("DNS setup: Failed to set system DNS: stderr={}, stdout={}", stderr, stdout);
+                tracing::warn!("DNS setup: Failed to set system DNS: stderr={}, stdout={}", stderr, stdout);
             }
         }
         Err(e) => {
-            println!("DNS setup: Failed to execute set_dns.sh: {}", e);
+            tracing::warn!("DNS setup: Failed to execute set_dns.sh: {}", e);
         }
     }
 }
@@ -117,7 +117,7 @@ async fn restore_system_dns(app: &tauri::AppHandle) {
     let script_path = match find_script(app, "unset_dns.sh") {
         Some(p) => p,
         None => {
-            println!("DNS restore: unset_dns.sh not found in any search path");
+            tracing::warn!("DNS restore: unset_dns.sh not found in any search path");
             return;
         }
     };
@@ -126,7 +126,7 @@ async fn restore_system_dns(app: &tauri::AppHandle) {
         .canonicalize()
         .unwrap_or_else(|_| script_path.clone());
 
-    println!(
+    tracing::info!(
         "DNS restore: Restoring original system DNS using {:?}",
         script_abs
     );
@@ -141,15 +141,15 @@ async fn restore_system_dns(app: &tauri::AppHandle) {
     match output {
         Ok(output) => {
             if output.status.success() {
-                println!("DNS restore: Successfully restored system DNS");
+                tracing::info!("DNS restore: Successfully restored system DNS");
             } else {
                 let stderr = String::from_utf8_lossy(&output.stderr);
                 let stdout = String::from_utf8_lossy(&output.stdout);
-                println!("DNS restore: Failed to restore system DNS: stderr={}, stdout={}", stderr, stdout);
+                tracing::warn!("DNS restore: Failed to restore system DNS: stderr={}, stdout={}", stderr, stdout);
             }
         }
         Err(e) => {
-            println!("DNS restore: Failed to execute unset_dns.sh: {}", e);
+            tracing::warn!("DNS restore: Failed to execute unset_dns.sh: {}", e);
         }
     }
 }
@@ -160,9 +160,8 @@ async fn restore_system_dns(app: &tauri::AppHandle) {
 #[allow(dead_code)]
 async fn restart_launchdaemon_with_osascript() -> Result<(), String> {
     const SERVICE_LABEL: &str = "com.aqiu.service";
-    const SERVICE_PLIST_PATH: &str = "/Library/LaunchDaemons/com.aqiu.service.plist";
     
-    println!("LaunchDaemon restart: Using osascript with admin privileges...");
+    tracing::info!("LaunchDaemon restart: Using osascript with admin privileges...");
     
     // Use osascript to run launchctl commands with admin privileges
     let restart_script = format!(
@@ -176,7 +175,7 @@ async fn restart_launchdaemon_with_osascript() -> Result<(), String> {
     
     match restart_result {
         Ok(output) if output.status.success() => {
-            println!("LaunchDaemon restart: Restarted successfully with admin privileges");
+            tracing::info!("LaunchDaemon restart: Restarted successfully with admin privileges");
             Ok(())
         }
         Ok(output) => {
@@ -184,10 +183,10 @@ async fn restart_launchdaemon_with_osascript() -> Result<(), String> {
             let stdout = String::from_utf8_lossy(&output.stdout);
             // Check if user cancelled the authorization
             if stderr.contains("-128") || stderr.contains("User canceled") {
-                println!("LaunchDaemon restart: User cancelled authorization");
+                tracing::warn!("LaunchDaemon restart: User cancelled authorization");
                 return Err("User cancelled authorization".into());
             }
-            println!("LaunchDaemon restart: Returned: stderr={}, stdout={}", stderr, stdout);
+            tracing::info!("LaunchDaemon restart: Returned: stderr={}, stdout={}", stderr, stdout);
             // Try kickstart as fallback
             let kickstart_script = format!(
                 r#"do shell script "launchctl kickstart -k system/{}" with administrator privileges"#,
@@ -198,14 +197,14 @@ async fn restart_launchdaemon_with_osascript() -> Result<(), String> {
                 .output()
             {
                 Ok(output) if output.status.success() => {
-                    println!("LaunchDaemon restart: Kickstart successful");
+                    tracing::info!("LaunchDaemon restart: Kickstart successful");
                     Ok(())
                 }
                 _ => Err("Failed to restart LaunchDaemon".into())
             }
         }
         Err(e) => {
-            println!("LaunchDaemon restart: Failed: {}", e);
+            tracing::warn!("LaunchDaemon restart: Failed: {}", e);
             Err(format!("Failed to restart LaunchDaemon: {}", e))
         }
     }
@@ -218,9 +217,19 @@ pub async fn set_tun_mode(
     state: tauri::State<'_, MihomoState>,
     enable: bool,
 ) -> Result<(), String> {
+    // Don't block: the user may know what they're doing, but the UI should say why
+    // the network could break
+    if enable {
+        if let Ok(interfaces) = detect_vpn_conflict(state.clone()).await {
+            if !interfaces.is_empty() {
+                let _ = app.emit("vpn-conflict", VpnConflictEvent { interfaces });
+            }
+        }
+    }
+
     #[cfg(target_os = "macos")]
     if enable && !is_privileged_helper_valid() {
-        println!("TUN Mode: Helper not installed, attempting auto-installation...");
+        tracing::info!("TUN Mode: Helper not installed, attempting auto-installation...");
         install_privileged_helper(app.clone(), state.clone())
             .await
             .map_err(|e| {
@@ -233,6 +242,19 @@ pub async fn set_tun_mode(
 
     #[cfg(any(target_os = "macos", target_os = "linux"))]
     {
+        // 0. Service Mode rewrites the system config below; make sure that can succeed
+        //    before changing anything
+        #[cfg(target_os = "macos")]
+        {
+            let is_service_mode = matches!(
+                state.current_mode.lock().ok().map(|m| *m),
+                Some(CoreMode::Service)
+            );
+            if is_service_mode && is_core_running(state.inner()) {
+                precheck_service_writable()?;
+            }
+        }
+
         // 1. Persist the user's preference FIRST
         if let Err(err) = crate::user_overrides::persist_tun_override(enable) {
             return Err(format!("Failed to save TUN preference: {}", err));
@@ -257,7 +279,7 @@ pub async fn set_tun_mode(
 
         // 3. If running, apply the new TUN setting
         if was_running {
-            println!("TUN mode change: Core is running, applying new TUN setting...");
+            tracing::info!("TUN mode change: Core is running, applying new TUN setting...");
             
             #[cfg(target_os = "macos")]
             {
@@ -266,110 +288,84 @@ pub async fn set_tun_mode(
                 
                 if matches!(current_mode, Some(CoreMode::Service)) {
                     // Service Mode: TUN changes require restart via official API
-                    println!("TUN mode change: Service Mode detected, restarting via mihomo API...");
+                    tracing::info!("TUN mode change: Service Mode detected, restarting via mihomo API...");
                     
                     // Build new config with TUN override and write to system path
+                    // Note: This path should be writable by the current user (set during service install)
+                    const SYSTEM_CONFIG_PATH: &str = "/Library/Application Support/aqiu/config.yaml";
                     let config_path = resolve_config_path(state.inner());
-                    let content = std::fs::read_to_string(&config_path)
-                        .map_err(|e| format!("Failed to read config: {}", e))?;
-                    
-                    let final_content = match serde_yaml::from_str::<serde_yaml::Value>(&content) {
-                        Ok(mut yaml) => {
-                            // DEBUG: Log overrides file path and content
-                            let overrides_path = dirs::data_local_dir()
-                                .unwrap_or_default()
-                                .join("aqiu")
-                                .join("user_overrides.json");
-                            println!("TUN mode change: Loading overrides from {:?}", overrides_path);
-                            if let Ok(overrides_content) = std::fs::read_to_string(&overrides_path) {
-                                println!("TUN mode change: Overrides content: {}", overrides_content);
+
+                    let overrides = crate::user_overrides::load_overrides();
+                    tracing::info!("TUN mode change: Loaded overrides - TUN enable: {:?}", 
+                        overrides.tun.as_ref().and_then(|t| t.enable));
+
+                    let final_content = match merge_config_with_overrides(
+                        &config_path,
+                        std::path::Path::new(SYSTEM_CONFIG_PATH),
+                        "service",
+                    ) {
+                        Ok(content) => content,
+                        Err(e) => {
+                            tracing::info!("TUN mode change: {}", e);
+                            tracing::info!("TUN mode change: Checking file permissions...");
+                            
+                            // Try to get file info for debugging
+                            if let Ok(metadata) = std::fs::metadata(SYSTEM_CONFIG_PATH) {
+                                tracing::info!("TUN mode change: Config file exists, readonly={}", metadata.permissions().readonly());
                             } else {
-                                println!("TUN mode change: WARNING - Could not read overrides file!");
+                                tracing::info!("TUN mode change: Config file does not exist or cannot be accessed");
                             }
                             
-                            let overrides = crate::user_overrides::load_overrides();
-                            println!("TUN mode change: Loaded overrides - TUN enable: {:?}", 
-                                overrides.tun.as_ref().and_then(|t| t.enable));
-                            if let Err(e) = crate::user_overrides::apply_overrides_to_yaml(&mut yaml, &overrides) {
-                                eprintln!("Warning: Failed to apply user overrides: {}", e);
-                                content.clone()
+                            // Check if directory is writable
+                            let test_path = "/Library/Application Support/aqiu/.write_test";
+                            if std::fs::write(test_path, "test").is_ok() {
+                                let _ = std::fs::remove_file(test_path);
+                                tracing::info!("TUN mode change: Directory is writable but config file is not");
                             } else {
-                                // DEBUG: Verify TUN section after applying overrides
-                                if let Some(tun) = yaml.get("tun") {
-                                    println!("TUN mode change: After apply - tun.enable = {:?}", 
-                                        tun.get("enable").and_then(|v| v.as_bool()));
-                                }
-                                serde_yaml::to_string(&yaml).unwrap_or(content.clone())
+                                tracing::warn!("TUN mode change: Directory is not writable - permission issue");
                             }
+                            
+                            return Err(format!("{}. Try reinstalling Service Mode.", e));
                         }
-                        Err(_) => content.clone(),
                     };
-                    
-                    // Write to system config path
-                    const SYSTEM_CONFIG_PATH: &str = "/Library/Application Support/aqiu/config.yaml";
+                    tracing::info!("TUN mode change: Successfully wrote config to {}", SYSTEM_CONFIG_PATH);
                     
                     // Debug: Log the TUN and DNS sections being written
                     if let Ok(yaml_check) = serde_yaml::from_str::<serde_yaml::Value>(&final_content) {
                         if let Some(tun_section) = yaml_check.get("tun") {
-                            println!("TUN mode change: Writing TUN config:");
+                            tracing::debug!("TUN mode change: Writing TUN config:");
                             if let Some(enable_val) = tun_section.get("enable") {
-                                println!("  - enable: {:?}", enable_val);
+                                tracing::debug!("  - enable: {:?}", enable_val);
                             }
                             if let Some(stack_val) = tun_section.get("stack") {
-                                println!("  - stack: {:?}", stack_val);
+                                tracing::debug!("  - stack: {:?}", stack_val);
                             }
                             if let Some(hijack_val) = tun_section.get("dns-hijack") {
-                                println!("  - dns-hijack: {:?}", hijack_val);
+                                tracing::debug!("  - dns-hijack: {:?}", hijack_val);
                             }
                             
                             if let Some(dns_section) = yaml_check.get("dns") {
-                                println!("TUN mode change: DNS config:");
+                                tracing::debug!("TUN mode change: DNS config:");
                                 if let Some(enable_val) = dns_section.get("enable") {
-                                    println!("  - enable: {:?}", enable_val);
+                                    tracing::debug!("  - enable: {:?}", enable_val);
                                 }
                                 if let Some(mode_val) = dns_section.get("enhanced-mode") {
-                                    println!("  - enhanced-mode: {:?}", mode_val);
+                                    tracing::debug!("  - enhanced-mode: {:?}", mode_val);
                                 }
                                 if let Some(listen_val) = dns_section.get("listen") {
-                                    println!("  - listen: {:?}", listen_val);
+                                    tracing::debug!("  - listen: {:?}", listen_val);
                                 }
                                 if let Some(nameserver_val) = dns_section.get("nameserver") {
-                                    println!("  - nameserver count: {}", 
+                                    tracing::debug!("  - nameserver count: {}", 
                                         nameserver_val.as_sequence().map(|s| s.len()).unwrap_or(0));
                                 }
                             } else {
-                                println!("TUN mode change: WARNING - No DNS section in config!");
+                                tracing::warn!("TUN mode change: WARNING - No DNS section in config!");
                             }
                         } else {
-                            println!("TUN mode change: WARNING - No TUN section in final config!");
-                        }
-                    }
-                    
-                    // Write config to system path
-                    // Note: This path should be writable by the current user (set during service install)
-                    if let Err(e) = std::fs::write(SYSTEM_CONFIG_PATH, &final_content) {
-                        println!("TUN mode change: Failed to write to system config: {}", e);
-                        println!("TUN mode change: Checking file permissions...");
-                        
-                        // Try to get file info for debugging
-                        if let Ok(metadata) = std::fs::metadata(SYSTEM_CONFIG_PATH) {
-                            println!("TUN mode change: Config file exists, readonly={}", metadata.permissions().readonly());
-                        } else {
-                            println!("TUN mode change: Config file does not exist or cannot be accessed");
+                            tracing::warn!("TUN mode change: WARNING - No TUN section in final config!");
                         }
-                        
-                        // Check if directory is writable
-                        let test_path = "/Library/Application Support/aqiu/.write_test";
-                        if std::fs::write(test_path, "test").is_ok() {
-                            let _ = std::fs::remove_file(test_path);
-                            println!("TUN mode change: Directory is writable but config file is not");
-                        } else {
-                            println!("TUN mode change: Directory is not writable - permission issue");
-                        }
-                        
-                        return Err(format!("Failed to write system config: {}. Try reinstalling Service Mode.", e));
                     }
-                    println!("TUN mode change: Successfully wrote config to {}", SYSTEM_CONFIG_PATH);
 
                     // Keep runtime config in sync for debugging / User Mode switching.
                     // This also addresses reports that `config.runtime.yaml` shows `tun.enable: false`
@@ -377,14 +373,14 @@ pub async fn set_tun_mode(
                     {
                         let runtime_dir = get_config_dir();
                         let _ = std::fs::create_dir_all(&runtime_dir);
-                        let runtime_path = runtime_dir.join("config.runtime.yaml");
+                        let runtime_path = runtime_dir.join(RUNTIME_CONFIG_FILE);
                         if let Err(e) = std::fs::write(&runtime_path, &final_content) {
-                            println!(
+                            tracing::warn!(
                                 "TUN mode change: Failed to sync runtime config {:?}: {}",
                                 runtime_path, e
                             );
                         } else {
-                            println!(
+                            tracing::info!(
                                 "TUN mode change: Synced runtime config: {:?}",
                                 runtime_path
                             );
@@ -424,29 +420,29 @@ pub async fn set_tun_mode(
                     });
                     req = req.json(&payload);
                     
-                    println!("TUN mode change: Reloading config via PUT /configs?force=true");
-                    println!("TUN mode change: Config path: {}", SYSTEM_CONFIG_PATH);
+                    tracing::info!("TUN mode change: Reloading config via PUT /configs?force=true");
+                    tracing::info!("TUN mode change: Config path: {}", SYSTEM_CONFIG_PATH);
                     
                     let resp = req.send().await;
                     
                     match resp {
                         Ok(r) if r.status().is_success() => {
-                            println!("TUN mode change: Config reload API returned success");
+                            tracing::info!("TUN mode change: Config reload API returned success");
                         }
                         Ok(r) => {
                             let status = r.status();
                             let error_text = r.text().await.unwrap_or_default();
-                            println!("TUN mode change: Config reload returned status {} - {}", status, error_text);
+                            tracing::warn!("TUN mode change: Config reload returned status {} - {}", status, error_text);
                         }
                         Err(e) => {
-                            println!("TUN mode change: Config reload API request failed: {}", e);
+                            tracing::warn!("TUN mode change: Config reload API request failed: {}", e);
                             return Err(format!("Failed to reload config: {}", e));
                         }
                     }
                     
                     // Wait for TUN interface and DNS to initialize
                     tokio::time::sleep(tokio::time::Duration::from_millis(2000)).await;
-                    println!("TUN mode change: Initialization wait completed");
+                    tracing::info!("TUN mode change: Initialization wait completed");
                     
                     // Set or restore system DNS based on TUN mode
                     // Following clash-verge-rev's approach:
@@ -467,14 +463,17 @@ pub async fn set_tun_mode(
                         "core-started",
                         serde_json::json!({ "success": true, "message": Some(format!("Core restarted with TUN mode {}", if enable { "enabled" } else { "disabled" })) }),
                     );
-                    println!("TUN mode change: Completed successfully");
+                    rearm_kill_switch(state.inner()).await;
+                    tracing::info!("TUN mode change: Completed successfully");
                 } else {
                     // User Mode: Need to restart (TUN in User Mode will be rejected anyway)
-                    println!("TUN mode change: User Mode detected, restarting core...");
+                    tracing::info!("TUN mode change: User Mode detected, restarting core...");
                     stop_core_inner(state.inner()).await?;
                     tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
                     
-                    let config_path = resolve_config_path(state.inner());
+                    // Start from the profile, not the generated runtime config, so
+                    // `profile_path` keeps pointing at the real profile
+                    let config_path = active_profile_config_path();
                     let options = StartOptions {
                         config_path: Some(config_path.to_string_lossy().to_string()),
                         external_controller: None,
@@ -503,7 +502,8 @@ pub async fn set_tun_mode(
                         "core-started",
                         serde_json::json!({ "success": true, "message": Some(format!("Core restarted with TUN mode {}", if enable { "enabled" } else { "disabled" })) }),
                     );
-                    println!("TUN mode change: Core restarted successfully");
+                    rearm_kill_switch(state.inner()).await;
+                    tracing::info!("TUN mode change: Core restarted successfully");
                 }
             }
             
@@ -513,7 +513,8 @@ pub async fn set_tun_mode(
                 stop_core_inner(state.inner()).await?;
                 tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
                 
-                let config_path = resolve_config_path(state.inner());
+                // Start from the profile, not the generated runtime config
+                let config_path = active_profile_config_path();
                 let options = StartOptions {
                     config_path: Some(config_path.to_string_lossy().to_string()),
                     external_controller: None,
@@ -533,10 +534,11 @@ pub async fn set_tun_mode(
                     "core-started",
                     serde_json::json!({ "success": true, "message": Some(format!("Core restarted with TUN mode {}", if enable { "enabled" } else { "disabled" })) }),
                 );
-                println!("TUN mode change: Core restarted successfully");
+                rearm_kill_switch(state.inner()).await;
+                tracing::info!("TUN mode change: Core restarted successfully");
             }
         } else {
-            println!("TUN mode change: Core is not running, saved preference for next start");
+            tracing::info!("TUN mode change: Core is not running, saved preference for next start");
         }
         
         // Emit event after successful TUN mode change
@@ -651,3 +653,222 @@ pub async fn get_tun_status(state: tauri::State<'_, MihomoState>) -> Result<bool
         .unwrap_or(false))
 }
 
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TunConfig {
+    /// "running" when read from the core's /configs, "config" when read from the file
+    pub source: String,
+    /// The `tun:` section as applied (empty object if the config has none)
+    pub tun: serde_json::Value,
+}
+
+/// Read the `tun:` section from the config file the core was started with
+fn read_tun_section_from_config(state: &MihomoState) -> Result<serde_json::Value, String> {
+    let config_path = resolve_config_path(state);
+    let content = std::fs::read_to_string(&config_path)
+        .map_err(|e| format!("Failed to read config {:?}: {}", config_path, e))?;
+    let yaml: serde_yaml::Value =
+        serde_yaml::from_str(&content).map_err(|e| format!("Invalid YAML in config: {}", e))?;
+
+    match yaml.get("tun") {
+        Some(tun) => serde_json::to_value(tun).map_err(|e| e.to_string()),
+        None => Ok(serde_json::json!({})),
+    }
+}
+
+/// Get the full TUN block as applied (stack, mtu, auto-route, dns-hijack, route exclusions...).
+/// Falls back to the config file when the API is unreachable, like `get_tun_status`.
+#[tauri::command]
+pub async fn get_tun_config(state: tauri::State<'_, MihomoState>) -> Result<TunConfig, String> {
+    match fetch_running_config(state.inner()).await {
+        Ok(config) => Ok(TunConfig {
+            source: "running".to_string(),
+            tun: config.get("tun").cloned().unwrap_or_else(|| serde_json::json!({})),
+        }),
+        Err(e) => {
+            tracing::debug!("get_tun_config: {}, falling back to the config file", e);
+            Ok(TunConfig {
+                source: "config".to_string(),
+                tun: read_tun_section_from_config(state.inner())?,
+            })
+        }
+    }
+}
+
+/// Get the CIDRs excluded from the TUN route
+#[tauri::command]
+pub fn get_tun_route_exclusions() -> Result<Vec<String>, String> {
+    Ok(crate::user_overrides::load_overrides()
+        .tun
+        .and_then(|tun| tun.route_exclude_address)
+        .unwrap_or_default())
+}
+
+/// Replace the CIDRs excluded from the TUN route and reload the core if they changed
+#[tauri::command]
+pub async fn set_tun_route_exclusions(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, MihomoState>,
+    entries: Vec<String>,
+) -> Result<Vec<String>, String> {
+    let previous = get_tun_route_exclusions()?;
+    let saved = crate::user_overrides::persist_tun_route_exclusions(entries)?;
+
+    // Routes are set up when the TUN device is created, so a change needs a reload
+    if saved != previous && is_core_running(state.inner()) {
+        tracing::info!("TUN route exclusions changed, reloading core...");
+        restart_core_with_overrides(&app, state.clone()).await?;
+    }
+
+    Ok(saved)
+}
+
+// ========== VPN Conflict Detection ==========
+
+#[derive(Debug, Serialize, Clone)]
+pub struct VpnConflictEvent {
+    pub interfaces: Vec<String>,
+}
+
+/// Name prefixes used by tunnel devices of common VPN clients
+#[cfg(not(target_os = "windows"))]
+const VPN_INTERFACE_PREFIXES: &[&str] = &[
+    "tun", "tap", "utun", "wg", "ppp", "ipsec", "tailscale", "zt", "nordlynx", "gpd", "cscotun",
+];
+
+/// Active tunnel interfaces on Linux: anything the kernel flags as a tun/tap device,
+/// plus well-known VPN names, that isn't administratively down
+#[cfg(target_os = "linux")]
+fn list_tunnel_interfaces() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/net") else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            let is_tunnel = path.join("tun_flags").exists()
+                || VPN_INTERFACE_PREFIXES.iter().any(|p| name.starts_with(p));
+            let operstate = std::fs::read_to_string(path.join("operstate")).unwrap_or_default();
+            (is_tunnel && operstate.trim() != "down").then_some(name)
+        })
+        .collect()
+}
+
+/// Active tunnel interfaces on macOS. The system keeps several utun devices up for
+/// iCloud/Continuity with only link-local IPv6, so an interface only counts once it
+/// carries an IPv4 address.
+#[cfg(target_os = "macos")]
+fn list_tunnel_interfaces() -> Vec<String> {
+    let Ok(output) = Command::new("ifconfig").output() else {
+        return Vec::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let mut interfaces = Vec::new();
+    let mut current: Option<(String, bool)> = None;
+    let mut has_ipv4 = false;
+    for line in stdout.lines().chain(std::iter::once("")) {
+        if !line.starts_with(|c: char| c.is_whitespace()) {
+            if let Some((name, up)) = current.take() {
+                if up && has_ipv4 && VPN_INTERFACE_PREFIXES.iter().any(|p| name.starts_with(p)) {
+                    interfaces.push(name);
+                }
+            }
+            has_ipv4 = false;
+            if let Some((name, rest)) = line.split_once(": ") {
+                current = Some((name.to_string(), rest.contains("<UP")));
+            }
+        } else if line.trim_start().starts_with("inet ") {
+            has_ipv4 = true;
+        }
+    }
+    interfaces
+}
+
+/// Connected adapters whose driver belongs to a VPN client (TAP, WireGuard, Wintun, ...)
+#[cfg(target_os = "windows")]
+fn list_tunnel_interfaces() -> Vec<String> {
+    use std::process::Command;
+
+    const VPN_DRIVER_KEYWORDS: &[&str] = &[
+        "tap-", "wireguard", "wintun", "vpn", "anyconnect", "fortinet", "pangp", "zerotier",
+        "tailscale",
+    ];
+
+    let Ok(output) = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-NetAdapter | Where-Object Status -eq 'Up' | ForEach-Object { $_.Name + '|' + $_.InterfaceDescription }",
+        ])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (name, description) = line.trim().split_once('|')?;
+            let description = description.to_lowercase();
+            VPN_DRIVER_KEYWORDS
+                .iter()
+                .any(|k| description.contains(k))
+                .then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// Whether mihomo's TUN is up and the name of its device. The running core reports
+/// the real name (e.g. `utun5`) when it knows it; otherwise fall back to the configured id.
+async fn own_tun_device(state: &MihomoState) -> (bool, Option<String>) {
+    if let Ok(config) = fetch_running_config(state).await {
+        let tun = config.get("tun");
+        let enabled = tun
+            .and_then(|t| t.get("enable"))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+        let device = tun
+            .and_then(|t| t.get("device"))
+            .and_then(|v| v.as_str())
+            .filter(|d| !d.is_empty())
+            .map(|d| d.to_string());
+        if enabled {
+            return (true, device);
+        }
+    }
+
+    let configured = crate::user_overrides::load_overrides()
+        .tun
+        .and_then(|tun| tun.device_id);
+    (false, configured)
+}
+
+/// List active tun/utun/VPN interfaces other than mihomo's own. Running TUN alongside
+/// another VPN usually ends in a routing loop, so a non-empty result is a conflict.
+#[tauri::command]
+pub async fn detect_vpn_conflict(state: tauri::State<'_, MihomoState>) -> Result<Vec<String>, String> {
+    let (tun_enabled, own_device) = own_tun_device(state.inner()).await;
+
+    // Without a device name (macOS picks a `utunN`), our own TUN can't be told apart
+    // from another VPN, so don't report anything while it is up
+    if tun_enabled && own_device.is_none() {
+        tracing::debug!("VPN conflict: own TUN device unknown, skipping detection");
+        return Ok(Vec::new());
+    }
+
+    let interfaces: Vec<String> = list_tunnel_interfaces()
+        .into_iter()
+        // mihomo names its device "Meta" unless told otherwise
+        .filter(|name| Some(name) != own_device.as_ref() && name != "Meta")
+        .collect();
+
+    if !interfaces.is_empty() {
+        tracing::info!("VPN conflict: other tunnel interfaces are active: {}", interfaces.join(", "));
+    }
+
+    Ok(interfaces)
+}
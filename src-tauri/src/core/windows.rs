@@ -45,14 +45,14 @@ pub async fn set_system_proxy_windows(
             .set_value("ProxyOverride", &"localhost;127.*;10.*;172.16.*;172.17.*;172.18.*;172.19.*;172.20.*;172.21.*;172.22.*;172.23.*;172.24.*;172.25.*;172.26.*;172.27.*;172.28.*;172.29.*;172.30.*;172.31.*;192.168.*;<local>")
             .map_err(|e| format!("Failed to set proxy override: {}", e))?;
 
-        println!("Windows system proxy enabled: {}", proxy_server);
+        tracing::info!("Windows system proxy enabled: {}", proxy_server);
     } else {
         // Set ProxyEnable to 0
         internet_settings
             .set_value("ProxyEnable", &0u32)
             .map_err(|e| format!("Failed to disable proxy: {}", e))?;
 
-        println!("Windows system proxy disabled");
+        tracing::info!("Windows system proxy disabled");
     }
 
     // Notify Windows that Internet settings have changed
@@ -137,7 +137,7 @@ pub fn kill_process_windows(pid: u32) -> Result<(), String> {
         .map_err(|e| format!("Failed to kill process: {}", e))?;
 
     if output.status.success() {
-        println!("Successfully killed process {}", pid);
+        tracing::info!("Successfully killed process {}", pid);
         Ok(())
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -149,7 +149,7 @@ pub fn kill_process_windows(pid: u32) -> Result<(), String> {
 #[cfg(target_os = "windows")]
 pub fn cleanup_port_windows(port: u16) -> Result<(), String> {
     if let Some(pid) = find_pid_by_port_windows(port) {
-        println!("Found process {} using port {}, attempting to kill...", pid, port);
+        tracing::info!("Found process {} using port {}, attempting to kill...", pid, port);
         kill_process_windows(pid)?;
         
         // Wait a bit for the port to be released
@@ -160,14 +160,14 @@ pub fn cleanup_port_windows(port: u16) -> Result<(), String> {
             return Err(format!("Port {} is still in use after killing process", port));
         }
         
-        println!("Port {} is now free", port);
+        tracing::info!("Port {} is now free", port);
         Ok(())
     } else {
         // Port is not in use or we couldn't find the process
         if is_port_in_use_windows(port) {
             Err(format!("Port {} is in use but couldn't find the process", port))
         } else {
-            println!("Port {} is not in use", port);
+            tracing::info!("Port {} is not in use", port);
             Ok(())
         }
     }
@@ -1,6 +1,7 @@
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 
 mod core;
+mod logging;
 mod profiles;
 mod service;
 mod user_overrides;
@@ -9,7 +10,7 @@ use core::MihomoState;
 use tauri::{
     menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager, State,
+    Emitter, Manager, State,
 };
 use tauri_plugin_autostart::MacosLauncher;
 
@@ -21,6 +22,92 @@ struct TrayMenuState {
     mode_direct: CheckMenuItem<tauri::Wry>,
 }
 
+#[derive(Clone, serde::Serialize)]
+struct SecondInstanceEvent {
+    args: Vec<String>,
+    cwd: String,
+}
+
+/// Bring the main window to the front (it may be hidden to tray or minimized)
+fn focus_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+struct DeepLinkFailedEvent {
+    error: String,
+}
+
+/// A subscription offered by a deep link, waiting for the user to confirm the import
+#[derive(Clone, serde::Serialize)]
+struct DeepLinkImportRequest {
+    url: String,
+    name: Option<String>,
+}
+
+/// Deep link imports not yet picked up by the UI (a link may launch the app before
+/// the frontend listens for events)
+#[derive(Default)]
+struct PendingDeepLinks(std::sync::Mutex<Vec<DeepLinkImportRequest>>);
+
+/// Parse a `clash://install-config` / `aqiu://install-config` deep link and queue it for
+/// confirmation. Nothing is fetched here: any web page can open such a link, so the
+/// import only happens through `confirm_deep_link_import`.
+#[tauri::command]
+fn handle_deep_link(
+    app: tauri::AppHandle,
+    pending: State<'_, PendingDeepLinks>,
+    url: String,
+) -> Result<(), String> {
+    focus_main_window(&app);
+
+    let (sub_url, name) = profiles::parse_install_config_link(&url)?;
+    tracing::info!("Deep link: subscription {:?} awaiting confirmation", name);
+
+    pending
+        .0
+        .lock()
+        .map_err(|e| e.to_string())?
+        .push(DeepLinkImportRequest { url: sub_url, name });
+    let _ = app.emit("deep-link-import-requested", ());
+
+    Ok(())
+}
+
+/// Hand the queued deep link imports to the UI
+#[tauri::command]
+fn take_pending_deep_links(
+    pending: State<'_, PendingDeepLinks>,
+) -> Result<Vec<DeepLinkImportRequest>, String> {
+    let mut queue = pending.0.lock().map_err(|e| e.to_string())?;
+    Ok(std::mem::take(&mut *queue))
+}
+
+/// Import a subscription from a deep link after the user confirmed it
+#[tauri::command]
+async fn confirm_deep_link_import(
+    app: tauri::AppHandle,
+    url: String,
+    name: Option<String>,
+) -> Result<profiles::Profile, String> {
+    let profile = profiles::import_profile_from_subscription(url, name).await?;
+    let _ = app.emit("profile-imported", profile.clone());
+
+    Ok(profile)
+}
+
+/// Handle a deep link delivered by the OS, reporting invalid links to the UI
+fn dispatch_deep_link(app: &tauri::AppHandle, url: String) {
+    if let Err(error) = handle_deep_link(app.clone(), app.state::<PendingDeepLinks>(), url) {
+        tracing::error!("Deep link rejected: {}", error);
+        let _ = app.emit("deep-link-failed", DeepLinkFailedEvent { error });
+    }
+}
+
 #[tauri::command]
 fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
@@ -98,17 +185,21 @@ struct ServiceStatusResult {
     status: String,
     version: Option<String>,
     needs_reinstall: bool,
+    /// Why a reinstall is needed (version mismatch, broken plist, missing binary, insecure socket)
+    reinstall_reason: Option<String>,
 }
 
 #[tauri::command]
 async fn service_check_status() -> Result<ServiceStatusResult, String> {
     let status = service::check_service_status().await;
 
-    let (status_str, needs_reinstall) = match &status {
-        service::ServiceStatus::Ready => ("ready".to_string(), false),
-        service::ServiceStatus::NeedsReinstall => ("needs_reinstall".to_string(), true),
-        service::ServiceStatus::NotInstalled => ("not_installed".to_string(), false),
-        service::ServiceStatus::Unavailable(e) => (format!("unavailable: {}", e), false),
+    let (status_str, reinstall_reason) = match &status {
+        service::ServiceStatus::Ready => ("ready".to_string(), None),
+        service::ServiceStatus::NeedsReinstall(reason) => {
+            ("needs_reinstall".to_string(), Some(reason.clone()))
+        }
+        service::ServiceStatus::NotInstalled => ("not_installed".to_string(), None),
+        service::ServiceStatus::Unavailable(e) => (format!("unavailable: {}", e), None),
     };
 
     let version = if status == service::ServiceStatus::Ready {
@@ -120,7 +211,8 @@ async fn service_check_status() -> Result<ServiceStatusResult, String> {
     Ok(ServiceStatusResult {
         status: status_str,
         version,
-        needs_reinstall,
+        needs_reinstall: reinstall_reason.is_some(),
+        reinstall_reason,
     })
 }
 
@@ -288,11 +380,7 @@ fn create_tray(app: &tauri::App) -> Result<TrayMenuState, Box<dyn std::error::Er
                 ..
             } = event
             {
-                let app = tray.app_handle();
-                if let Some(window) = app.get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                }
+                focus_main_window(tray.app_handle());
             }
         })
         .build(app)?;
@@ -308,7 +396,18 @@ fn create_tray(app: &tauri::App) -> Result<TrayMenuState, Box<dyn std::error::Er
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    logging::init_logging();
+
     tauri::Builder::default()
+        // Must be registered first: a second launch exits here, before `setup` runs,
+        // so it never creates a tray, binds the controller port, or auto-starts a core.
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            tracing::info!("Second instance launched with args: {:?}", args);
+            focus_main_window(app);
+            // Forward CLI args (e.g. a profile URL to import) to the running instance
+            let _ = app.emit("second-instance", SecondInstanceEvent { args, cwd });
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_clipboard_manager::init())
@@ -319,10 +418,54 @@ pub fn run() {
             Some(vec!["--minimized"]),
         ))
         .manage(MihomoState::default())
+        .manage(PendingDeepLinks::default())
         .setup(|app| {
             let tray_state = create_tray(app)?;
             app.manage(tray_state);
 
+            // One-click subscription import via clash:// and aqiu:// links.
+            // Links opened while running are forwarded here by the single-instance plugin.
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+
+                // macOS registers schemes from the bundle; Linux (and dev builds on
+                // Windows) need a runtime registration
+                #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+                if let Err(e) = app.deep_link().register_all() {
+                    tracing::error!("Failed to register deep link schemes: {}", e);
+                }
+
+                let app_handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    for url in event.urls() {
+                        dispatch_deep_link(&app_handle, url.to_string());
+                    }
+                });
+
+                // The app may have been launched by a link
+                if let Ok(Some(urls)) = app.deep_link().get_current() {
+                    for url in urls {
+                        dispatch_deep_link(app.handle(), url.to_string());
+                    }
+                }
+            }
+
+            // Background geodata refresh (gated by `geodata-update-interval-days`)
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    core::run_geodata_auto_update(app_handle).await;
+                });
+            }
+
+            // Scheduled core start/stop (see `core::set_core_schedule`)
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    core::run_core_schedule(app_handle).await;
+                });
+            }
+
             // On startup: restore core mode preference, recover orphaned core, then auto-start if needed
             #[cfg(target_os = "macos")]
             {
@@ -338,7 +481,7 @@ pub fn run() {
                         };
                         if let Ok(mut desired) = state.desired_mode.lock() {
                             *desired = target_mode;
-                            println!("Startup: Restored core mode preference: {:?}", target_mode);
+                            tracing::info!("Startup: Restored core mode preference: {:?}", target_mode);
                         }
                         if let Ok(mut current) = state.current_mode.lock() {
                             *current = target_mode;
@@ -351,7 +494,7 @@ pub fn run() {
                         .unwrap_or(false);
                     
                     if recovered {
-                        println!("Startup: Recovered orphaned core process, skipping auto-start");
+                        tracing::info!("Startup: Recovered orphaned core process, skipping auto-start");
                         return;
                     }
                     
@@ -370,7 +513,7 @@ pub fn run() {
                     };
                     
                     if should_auto_start {
-                        println!("Startup: Auto-starting core in {:?} mode...", 
+                        tracing::info!("Startup: Auto-starting core in {:?} mode...", 
                             if is_service_mode { "Service" } else { "User" });
                         let start_result = core::start_core(app_handle.clone(), state.clone(), None).await;
                         
@@ -379,8 +522,8 @@ pub fn run() {
                             // Wait a bit for core to fully initialize
                             tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
                             
-                            // GEO files auto-download is DISABLED
-                            // User should manually sync GEO files via Settings if needed
+                            // Missing/stale GEO files are refreshed by the background
+                            // geodata task (see `core::run_geodata_auto_update`)
                             let config_dir = core::get_config_dir();
                             let geoip_path = config_dir.join("geoip.dat");
                             let geosite_path = config_dir.join("geosite.dat");
@@ -388,18 +531,18 @@ pub fn run() {
                             let geosite_exists = geosite_path.exists();
                             
                             if !geoip_exists || !geosite_exists {
-                                println!("Startup: GEO database incomplete:");
+                                tracing::info!("Startup: GEO database incomplete:");
                                 if !geoip_exists {
-                                    println!("  - geoip.dat not found at: {:?}", geoip_path);
+                                    tracing::debug!("  - geoip.dat not found at: {:?}", geoip_path);
                                 }
                                 if !geosite_exists {
-                                    println!("  - geosite.dat not found at: {:?}", geosite_path);
+                                    tracing::debug!("  - geosite.dat not found at: {:?}", geosite_path);
                                 }
-                                println!("Startup: Auto-download disabled. Use Settings -> Update GEO to download manually.");
+                                tracing::info!("Startup: GEO auto-update will download them shortly (or use Settings -> Update GEO).");
                             } else {
-                                println!("Startup: GEO database exists:");
-                                println!("  - geoip.dat: {:?}", geoip_path);
-                                println!("  - geosite.dat: {:?}", geosite_path);
+                                tracing::info!("Startup: GEO database exists:");
+                                tracing::debug!("  - geoip.dat: {:?}", geoip_path);
+                                tracing::debug!("  - geosite.dat: {:?}", geosite_path);
                             }
                         }
                     }
@@ -422,13 +565,40 @@ pub fn run() {
             core::get_system_proxy_status,
             core::set_tun_mode,
             core::get_tun_status,
+            core::get_tun_route_exclusions,
+            core::set_tun_route_exclusions,
+            core::get_tun_config,
+            core::detect_vpn_conflict,
+            core::run_leak_test,
+            core::get_base_template,
+            core::set_base_template,
+            core::clear_base_template,
+            core::get_core_schedule,
+            core::set_core_schedule,
+            core::get_auto_update_pause_status,
+            core::set_pause_updates_on_metered,
+            core::pause_auto_updates,
+            core::list_installed_cores,
+            core::select_core,
+            core::rediscover_controller,
             core::set_mode,
             core::get_mode,
+            core::apply_overrides_live,
+            core::refresh_active_config_path,
+            core::get_fake_ip_filter,
+            core::set_fake_ip_filter_user_entries,
+            core::diagnose_dns,
+            core::apply_dns_fix,
+            core::set_kill_switch,
             core::copy_proxy_env,
             core::download_core,
             core::download_geodata,
+            core::get_geodata_status,
+            core::list_config_backups,
+            core::restore_config_backup,
             core::import_core_binary,
             core::check_core_exists,
+            core::check_config_compatibility,
             core::get_app_paths,
             core::download_profile,
             #[cfg(target_os = "macos")]
@@ -438,6 +608,8 @@ pub fn run() {
             #[cfg(target_os = "macos")]
             core::uninstall_privileged_helper,
             #[cfg(target_os = "macos")]
+            core::precheck_service_writable,
+            #[cfg(target_os = "macos")]
             core::get_core_mode,
             #[cfg(target_os = "macos")]
             core::get_desired_core_mode,
@@ -461,6 +633,12 @@ pub fn run() {
             profiles::add_proxy_to_profile,
             profiles::parse_proxy_url,
             profiles::get_active_profile_path,
+            profiles::export_subscription,
+            logging::set_app_log_level,
+            logging::get_app_logs,
+            handle_deep_link,
+            take_pending_deep_links,
+            confirm_deep_link_import,
             user_overrides::set_user_override,
             user_overrides::get_user_overrides,
             user_overrides::clear_user_overrides,
@@ -0,0 +1,106 @@
+// ========== App Logging ==========
+//
+// Backend diagnostics go through `tracing` to stdout and to a daily rolling file
+// next to the core logs. The level can be changed at runtime from the UI.
+
+use std::sync::OnceLock;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::{filter::LevelFilter, fmt, prelude::*, reload, Registry};
+
+const LOG_FILE_PREFIX: &str = "aqiu";
+const LOG_FILE_SUFFIX: &str = "log";
+const MAX_LOG_FILES: usize = 7;
+const DEFAULT_LOG_LINES: usize = 200;
+
+static LEVEL_HANDLE: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+static FILE_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+
+fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    match level.trim().to_lowercase().as_str() {
+        "trace" => Ok(LevelFilter::TRACE),
+        "debug" => Ok(LevelFilter::DEBUG),
+        "info" => Ok(LevelFilter::INFO),
+        "warn" | "warning" => Ok(LevelFilter::WARN),
+        "error" => Ok(LevelFilter::ERROR),
+        "off" | "silent" => Ok(LevelFilter::OFF),
+        other => Err(format!("Unsupported log level: {}", other)),
+    }
+}
+
+/// Install the global subscriber. `AQIU_LOG` picks the initial level (default info).
+pub fn init_logging() {
+    let initial = std::env::var("AQIU_LOG")
+        .ok()
+        .and_then(|level| parse_level(&level).ok())
+        .unwrap_or(LevelFilter::INFO);
+    let (level_layer, handle) = reload::Layer::new(initial);
+
+    let file_layer = match Builder::new()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(crate::core::get_logs_dir())
+    {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            let _ = FILE_GUARD.set(guard);
+            Some(fmt::layer().with_ansi(false).with_writer(writer))
+        }
+        Err(e) => {
+            eprintln!("Failed to open app log file, logging to stdout only: {}", e);
+            None
+        }
+    };
+
+    if tracing_subscriber::registry()
+        .with(level_layer)
+        .with(file_layer)
+        .with(fmt::layer())
+        .try_init()
+        .is_ok()
+    {
+        let _ = LEVEL_HANDLE.set(handle);
+    }
+}
+
+/// Change the verbosity of the app's own logs
+#[tauri::command]
+pub fn set_app_log_level(level: String) -> Result<(), String> {
+    let filter = parse_level(&level)?;
+    LEVEL_HANDLE
+        .get()
+        .ok_or("Logging is not initialized")?
+        .reload(filter)
+        .map_err(|e| format!("Failed to set log level: {}", e))?;
+    tracing::info!("App log level set to {}", filter);
+    Ok(())
+}
+
+/// Last `lines` lines of the newest app log file (default 200)
+#[tauri::command]
+pub fn get_app_logs(lines: Option<usize>) -> Result<Vec<String>, String> {
+    let dir = crate::core::get_logs_dir();
+    let prefix = format!("{}.", LOG_FILE_PREFIX);
+    let suffix = format!(".{}", LOG_FILE_SUFFIX);
+
+    // Rolled files are named aqiu.YYYY-MM-DD.log, so the newest sorts last
+    let latest = std::fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read logs dir {:?}: {}", dir, e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(&prefix) && name.ends_with(&suffix))
+        .max();
+
+    let Some(latest) = latest else {
+        return Ok(Vec::new());
+    };
+
+    let content = std::fs::read_to_string(dir.join(&latest))
+        .map_err(|e| format!("Failed to read log file {}: {}", latest, e))?;
+    let all: Vec<&str> = content.lines().collect();
+    let count = lines.unwrap_or(DEFAULT_LOG_LINES).min(all.len());
+
+    Ok(all[all.len() - count..].iter().map(|line| line.to_string()).collect())
+}
@@ -131,6 +131,16 @@ fn save_profiles_data(data: &ProfilesData) -> Result<(), String> {
     Ok(())
 }
 
+/// Resolve a profile's config file path by id
+pub fn get_profile_path(id: &str) -> Result<String, String> {
+    load_profiles_data()
+        .profiles
+        .into_iter()
+        .find(|p| p.id == id)
+        .map(|p| p.file_path)
+        .ok_or_else(|| "Profile not found".to_string())
+}
+
 fn generate_id() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
     let now = SystemTime::now()
@@ -640,41 +650,22 @@ fn parse_proxy_url_value(url: &str) -> Result<serde_json::Value, String> {
             "udp": true
         }));
     } else if url.starts_with("trojan://") {
-        // trojan://password@host:port#name
-        let without_prefix = &url[9..];
-        let (main_part, name) = if let Some(pos) = without_prefix.find('#') {
-            (&without_prefix[..pos], Some(&without_prefix[pos + 1..]))
-        } else {
-            (without_prefix, None)
-        };
-
-        let (password, server_part) = if let Some(pos) = main_part.find('@') {
-            (&main_part[..pos], &main_part[pos + 1..])
-        } else {
-            return Err("Invalid Trojan URL: missing @".to_string());
-        };
-
-        let server_parts: Vec<&str> = server_part.splitn(2, ':').collect();
-        if server_parts.len() < 2 {
-            return Err("Invalid Trojan server: missing port".to_string());
+        // trojan://password@host:port?sni=...&allowInsecure=1&type=ws#name
+        let parsed = parse_standard_url(url)?;
+        let password = parsed.userinfo.ok_or("Invalid Trojan URL: missing @")?;
+        let name = parsed
+            .name
+            .unwrap_or_else(|| format!("Trojan-{}-{}", parsed.host, parsed.port));
+
+        let mut map = build_base_proxy(Some(name), "trojan", &parsed.host, parsed.port);
+        set_string(&mut map, "password", &password);
+        set_string(&mut map, "sni", &parsed.host);
+        apply_common_query(&mut map, &parsed.query);
+        if let Some(value) = parsed.query.get("allowInsecure") {
+            set_bool(&mut map, "skip-cert-verify", value);
         }
 
-        let server = server_parts[0];
-        let port = server_parts[1].parse::<u16>().map_err(|e| e.to_string())?;
-
-        let name = name
-            .map(|n| urlencoding::decode(n).unwrap_or(n.into()).into_owned())
-            .unwrap_or_else(|| format!("Trojan-{}-{}", server, port));
-
-        return Ok(serde_json::json!({
-            "name": name,
-            "type": "trojan",
-            "server": server,
-            "port": port,
-            "password": password,
-            "udp": true,
-            "sni": server
-        }));
+        return Ok(serde_json::Value::Object(map));
     } else if url.starts_with("ssr://") {
         return parse_ssr_url(url);
     } else if url.starts_with("vless://")
@@ -988,6 +979,359 @@ fn build_config_from_proxy_urls(urls: &[String]) -> Result<serde_yaml::Value, St
     Ok(serde_yaml::Value::Mapping(root))
 }
 
+// ========== Proxy URL Serialization ==========
+
+/// Read a scalar field as a string (numbers and bools included); empty values count as missing
+fn proxy_field(proxy: &serde_json::Value, key: &str) -> Option<String> {
+    let value = match proxy.get(key)? {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::Bool(b) => b.to_string(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .filter_map(|item| match item {
+                serde_json::Value::String(s) => Some(s.clone()),
+                serde_json::Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        _ => return None,
+    };
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+fn proxy_flag(proxy: &serde_json::Value, key: &str) -> bool {
+    proxy.get(key).and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+fn format_host_port(host: &str, port: &str) -> String {
+    if host.contains(':') && !host.starts_with('[') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+fn push_query(query: &mut Vec<String>, key: &str, value: Option<String>) {
+    if let Some(value) = value {
+        query.push(format!("{}={}", key, urlencoding::encode(&value)));
+    }
+}
+
+/// Inverse of `apply_common_query`
+fn common_query(proxy: &serde_json::Value) -> Vec<String> {
+    let mut query = Vec::new();
+    push_query(
+        &mut query,
+        "sni",
+        proxy_field(proxy, "sni").or_else(|| proxy_field(proxy, "servername")),
+    );
+    push_query(&mut query, "alpn", proxy_field(proxy, "alpn"));
+    if proxy_flag(proxy, "skip-cert-verify") {
+        push_query(&mut query, "insecure", Some("1".to_string()));
+    }
+    push_query(&mut query, "fp", proxy_field(proxy, "client-fingerprint"));
+    push_query(&mut query, "type", proxy_field(proxy, "network"));
+
+    let ws_opts = proxy.get("ws-opts");
+    let path = proxy_field(proxy, "path").or_else(|| ws_opts.and_then(|o| proxy_field(o, "path")));
+    let host = proxy_field(proxy, "host").or_else(|| {
+        ws_opts
+            .and_then(|o| o.get("headers"))
+            .and_then(|h| proxy_field(h, "Host"))
+    });
+    push_query(&mut query, "path", path);
+    push_query(&mut query, "host", host);
+    query
+}
+
+fn build_standard_url(
+    scheme: &str,
+    userinfo: Option<String>,
+    server: &str,
+    port: &str,
+    query: Vec<String>,
+    name: &str,
+) -> String {
+    let mut url = format!("{}://", scheme);
+    if let Some(info) = userinfo {
+        url.push_str(&info);
+        url.push('@');
+    }
+    url.push_str(&format_host_port(server, port));
+    if !query.is_empty() {
+        url.push('?');
+        url.push_str(&query.join("&"));
+    }
+    url.push('#');
+    url.push_str(&urlencoding::encode(name));
+    url
+}
+
+/// `user:pass` userinfo with each part percent-encoded
+fn encode_credentials(user: Option<String>, pass: Option<String>) -> Option<String> {
+    let user = user?;
+    Some(match pass {
+        Some(pass) => format!(
+            "{}:{}",
+            urlencoding::encode(&user),
+            urlencoding::encode(&pass)
+        ),
+        None => urlencoding::encode(&user).into_owned(),
+    })
+}
+
+/// Serialize a Clash proxy back into a share link, mirroring `parse_proxy_url_value`.
+/// Returns None for proxy types that have no share-link form.
+fn proxy_to_url(proxy: &serde_json::Value) -> Option<String> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    let proxy_type = proxy_field(proxy, "type")?.to_lowercase();
+    let name = proxy_field(proxy, "name")?;
+    let server = proxy_field(proxy, "server")?;
+    let port = proxy_field(proxy, "port")?;
+
+    match proxy_type.as_str() {
+        "ss" => {
+            // The parser has no plugin support, so a link without it would not connect
+            if proxy.get("plugin").is_some() || proxy.get("plugin-opts").is_some() {
+                return None;
+            }
+            let auth = format!(
+                "{}:{}",
+                proxy_field(proxy, "cipher")?,
+                proxy_field(proxy, "password")?
+            );
+            Some(format!(
+                "ss://{}@{}#{}",
+                general_purpose::STANDARD.encode(auth),
+                format_host_port(&server, &port),
+                urlencoding::encode(&name)
+            ))
+        }
+        "ssr" => {
+            let main = format!(
+                "{}:{}:{}:{}:{}:{}",
+                server,
+                port,
+                proxy_field(proxy, "protocol")?,
+                proxy_field(proxy, "cipher")?,
+                proxy_field(proxy, "obfs")?,
+                general_purpose::STANDARD.encode(proxy_field(proxy, "password")?)
+            );
+            let mut params = vec![format!(
+                "remarks={}",
+                general_purpose::STANDARD.encode(&name)
+            )];
+            if let Some(value) = proxy_field(proxy, "obfs-param") {
+                params.push(format!(
+                    "obfsparam={}",
+                    general_purpose::STANDARD.encode(value)
+                ));
+            }
+            if let Some(value) = proxy_field(proxy, "protocol-param") {
+                params.push(format!(
+                    "protoparam={}",
+                    general_purpose::STANDARD.encode(value)
+                ));
+            }
+            Some(format!(
+                "ssr://{}",
+                general_purpose::STANDARD.encode(format!("{}/?{}", main, params.join("&")))
+            ))
+        }
+        "vmess" => {
+            let ws_opts = proxy.get("ws-opts");
+            let vmess_json = serde_json::json!({
+                "v": "2",
+                "ps": name,
+                "add": server,
+                "port": port.parse::<u64>().ok()?,
+                "id": proxy_field(proxy, "uuid")?,
+                "aid": proxy.get("alterId").and_then(|v| v.as_u64()).unwrap_or(0),
+                "scy": proxy_field(proxy, "cipher").unwrap_or_else(|| "auto".to_string()),
+                "net": proxy_field(proxy, "network").unwrap_or_else(|| "tcp".to_string()),
+                "type": "none",
+                "host": ws_opts
+                    .and_then(|o| o.get("headers"))
+                    .and_then(|h| proxy_field(h, "Host"))
+                    .unwrap_or_default(),
+                "path": ws_opts.and_then(|o| proxy_field(o, "path")).unwrap_or_default(),
+                "tls": if proxy_flag(proxy, "tls") { "tls" } else { "" },
+                "sni": proxy_field(proxy, "servername").unwrap_or_default(),
+            });
+            Some(format!(
+                "vmess://{}",
+                general_purpose::STANDARD.encode(vmess_json.to_string())
+            ))
+        }
+        "trojan" => {
+            let mut query = common_query(proxy);
+            // `allowInsecure` is what most trojan clients read
+            if proxy_flag(proxy, "skip-cert-verify") {
+                push_query(&mut query, "allowInsecure", Some("1".to_string()));
+            }
+            let password = urlencoding::encode(&proxy_field(proxy, "password")?).into_owned();
+            Some(build_standard_url(
+                "trojan",
+                Some(password),
+                &server,
+                &port,
+                query,
+                &name,
+            ))
+        }
+        "vless" => {
+            let mut query = Vec::new();
+            push_query(&mut query, "encryption", proxy_field(proxy, "encryption"));
+            push_query(&mut query, "flow", proxy_field(proxy, "flow"));
+            let reality = proxy.get("reality-opts");
+            if reality.is_some() {
+                push_query(&mut query, "security", Some("reality".to_string()));
+            } else if proxy_flag(proxy, "tls") {
+                push_query(&mut query, "security", Some("tls".to_string()));
+            }
+            if let Some(opts) = reality {
+                push_query(&mut query, "pbk", proxy_field(opts, "public-key"));
+                push_query(&mut query, "sid", proxy_field(opts, "short-id"));
+                push_query(&mut query, "spx", proxy_field(opts, "spider-x"));
+            }
+            query.extend(common_query(proxy));
+            let uuid = urlencoding::encode(&proxy_field(proxy, "uuid")?).into_owned();
+            Some(build_standard_url(
+                "vless",
+                Some(uuid),
+                &server,
+                &port,
+                query,
+                &name,
+            ))
+        }
+        "socks5" => {
+            let userinfo = encode_credentials(
+                proxy_field(proxy, "username"),
+                proxy_field(proxy, "password"),
+            );
+            let query = common_query(proxy);
+            Some(build_standard_url(
+                "socks5", userinfo, &server, &port, query, &name,
+            ))
+        }
+        "http" => {
+            let scheme = if proxy_flag(proxy, "tls") {
+                "https"
+            } else {
+                "http"
+            };
+            let userinfo = encode_credentials(
+                proxy_field(proxy, "username"),
+                proxy_field(proxy, "password"),
+            );
+            let query = common_query(proxy)
+                .into_iter()
+                .filter(|pair| !pair.starts_with("tls="))
+                .collect();
+            Some(build_standard_url(
+                scheme, userinfo, &server, &port, query, &name,
+            ))
+        }
+        "hysteria" => {
+            let mut query = Vec::new();
+            push_query(&mut query, "auth", proxy_field(proxy, "auth-str"));
+            push_query(&mut query, "up", proxy_field(proxy, "up"));
+            push_query(&mut query, "down", proxy_field(proxy, "down"));
+            push_query(&mut query, "obfs", proxy_field(proxy, "obfs"));
+            query.extend(common_query(proxy));
+            Some(build_standard_url(
+                "hysteria", None, &server, &port, query, &name,
+            ))
+        }
+        "hysteria2" => {
+            let mut query = Vec::new();
+            push_query(&mut query, "obfs", proxy_field(proxy, "obfs"));
+            push_query(
+                &mut query,
+                "obfs-password",
+                proxy_field(proxy, "obfs-password"),
+            );
+            query.extend(common_query(proxy));
+            let password =
+                proxy_field(proxy, "password").map(|p| urlencoding::encode(&p).into_owned());
+            Some(build_standard_url(
+                "hysteria2",
+                password,
+                &server,
+                &port,
+                query,
+                &name,
+            ))
+        }
+        "tuic" => {
+            let mut query = Vec::new();
+            push_query(
+                &mut query,
+                "congestion_control",
+                proxy_field(proxy, "congestion-controller"),
+            );
+            push_query(
+                &mut query,
+                "udp_relay_mode",
+                proxy_field(proxy, "udp-relay-mode"),
+            );
+            query.extend(common_query(proxy));
+            let userinfo =
+                encode_credentials(proxy_field(proxy, "uuid"), proxy_field(proxy, "password"));
+            Some(build_standard_url(
+                "tuic", userinfo, &server, &port, query, &name,
+            ))
+        }
+        "wireguard" => {
+            let mut query = Vec::new();
+            push_query(&mut query, "public_key", proxy_field(proxy, "public-key"));
+            push_query(
+                &mut query,
+                "pre_shared_key",
+                proxy_field(proxy, "pre-shared-key"),
+            );
+            push_query(&mut query, "reserved", proxy_field(proxy, "reserved"));
+            push_query(&mut query, "mtu", proxy_field(proxy, "mtu"));
+            push_query(&mut query, "address", proxy_field(proxy, "ip"));
+            query.extend(common_query(proxy));
+            let private_key =
+                proxy_field(proxy, "private-key").map(|k| urlencoding::encode(&k).into_owned());
+            Some(build_standard_url(
+                "wireguard",
+                private_key,
+                &server,
+                &port,
+                query,
+                &name,
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Built-in outbounds that may appear in `proxies` but aren't real servers
+fn is_pseudo_proxy(proxy: &serde_yaml::Value) -> bool {
+    const PSEUDO: [&str; 5] = ["direct", "reject", "reject-drop", "pass", "compatible"];
+    let field = |key: &str| {
+        proxy
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_lowercase())
+    };
+    [field("name"), field("type")]
+        .iter()
+        .flatten()
+        .any(|value| PSEUDO.contains(&value.as_str()))
+}
+
 // ========== Commands ==========
 
 #[tauri::command]
@@ -1037,8 +1381,24 @@ pub fn create_profile_from_path(
     create_profile_with_content(name, url, new_content)
 }
 
+/// Keep the running core on the active profile after it may have changed
+pub async fn realign_core_with_active_profile(app: &tauri::AppHandle) -> Result<(), String> {
+    use tauri::Manager;
+
+    let state = app.state::<crate::core::MihomoState>();
+    crate::core::refresh_active_config_path_inner(app, state)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Failed to realign core with active profile: {}", e))
+}
+
 #[tauri::command]
-pub fn delete_profile(id: String) -> Result<(), String> {
+pub async fn delete_profile(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    remove_profile(&id)?;
+    realign_core_with_active_profile(&app).await
+}
+
+fn remove_profile(id: &str) -> Result<(), String> {
     let mut data = load_profiles_data();
 
     if let Some(pos) = data.profiles.iter().position(|p| p.id == id) {
@@ -1049,7 +1409,7 @@ pub fn delete_profile(id: String) -> Result<(), String> {
 
         data.profiles.remove(pos);
 
-        if data.active_id.as_ref() == Some(&id) {
+        if data.active_id.as_deref() == Some(id) {
             data.active_id = data.profiles.first().map(|p| p.id.clone());
         }
 
@@ -1059,8 +1419,12 @@ pub fn delete_profile(id: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Mark a profile active. The core is not restarted here: the UI restarts it right after
+/// activating, so only a stopped core's paths are updated.
 #[tauri::command]
-pub fn set_active_profile(id: String) -> Result<(), String> {
+pub async fn set_active_profile(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    use tauri::Manager;
+
     let mut data = load_profiles_data();
 
     // Verify profile exists
@@ -1068,6 +1432,21 @@ pub fn set_active_profile(id: String) -> Result<(), String> {
         return Err("Profile not found".to_string());
     }
 
+    // Refuse a profile the installed core can't load. A missing core is not the
+    // profile's fault, and a check that can't run shouldn't block activation.
+    match crate::core::check_config_compatibility(id.clone()).await {
+        Ok(result) if !result.compatible && result.issue.as_deref() != Some("core_missing") => {
+            return Err(format!(
+                "{}: {}\n{}",
+                result.issue.unwrap_or_default(),
+                result.message.unwrap_or_default(),
+                result.suggestion.unwrap_or_default()
+            ));
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Skipping compatibility check for profile {}: {}", id, e),
+    }
+
     // Update is_active flags
     for p in &mut data.profiles {
         p.is_active = p.id == id;
@@ -1076,7 +1455,7 @@ pub fn set_active_profile(id: String) -> Result<(), String> {
     data.active_id = Some(id);
     save_profiles_data(&data)?;
 
-    Ok(())
+    crate::core::sync_active_config_path(app.state::<crate::core::MihomoState>().inner())
 }
 
 #[tauri::command]
@@ -1104,6 +1483,11 @@ pub fn save_profile_content(id: String, content: String) -> Result<(), String> {
 
     let normalized = normalize_config_content(&content)?;
     let new_content = serde_yaml::to_string(&normalized).map_err(|e| e.to_string())?;
+    crate::core::snapshot_config(
+        std::path::Path::new(&profile.file_path),
+        &format!("profile-{}", id),
+        &new_content,
+    );
     fs::write(&profile.file_path, &new_content).map_err(|e| e.to_string())?;
 
     profile.updated_at = get_current_time();
@@ -1203,6 +1587,100 @@ pub async fn update_profile_from_url(id: String) -> Result<String, String> {
     Ok("Updated successfully".to_string())
 }
 
+/// Parse a `clash://install-config?url=...&name=...` (or `aqiu://`) deep link
+/// into the subscription URL and optional profile name
+pub fn parse_install_config_link(link: &str) -> Result<(String, Option<String>), String> {
+    let parsed = tauri::Url::parse(link.trim()).map_err(|e| format!("Invalid deep link: {}", e))?;
+
+    if !matches!(parsed.scheme(), "clash" | "aqiu") {
+        return Err(format!("Unsupported deep link scheme: {}", parsed.scheme()));
+    }
+    if parsed.host_str() != Some("install-config") {
+        return Err("Unsupported deep link action (expected install-config)".to_string());
+    }
+
+    // query_pairs() already percent-decodes the values
+    let mut sub_url = None;
+    let mut name = None;
+    for (key, value) in parsed.query_pairs() {
+        match key.as_ref() {
+            "url" => sub_url = Some(value.into_owned()),
+            "name" => name = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+
+    let mut sub_url = sub_url
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .ok_or("Deep link is missing the url parameter")?;
+
+    // Some providers encode the subscription URL twice
+    if !sub_url.contains("://") {
+        if let Ok(decoded) = urlencoding::decode(&sub_url) {
+            sub_url = decoded.into_owned();
+        }
+    }
+
+    ensure_http_url(&sub_url)?;
+
+    let name = name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+
+    Ok((sub_url, name))
+}
+
+/// Subscriptions are only fetched over http(s), never `file://` or other schemes
+fn ensure_http_url(url: &str) -> Result<(), String> {
+    let target = tauri::Url::parse(url).map_err(|e| format!("Invalid subscription URL: {}", e))?;
+    if !matches!(target.scheme(), "http" | "https") {
+        return Err("Subscription URL must use http or https".to_string());
+    }
+    Ok(())
+}
+
+/// Create a profile for a subscription URL, or refresh the existing profile with
+/// the same URL, and download its content
+pub async fn import_profile_from_subscription(
+    url: String,
+    name: Option<String>,
+) -> Result<Profile, String> {
+    ensure_http_url(&url)?;
+
+    let existing = load_profiles_data()
+        .profiles
+        .into_iter()
+        .find(|p| p.url.as_deref() == Some(url.as_str()));
+
+    let (profile, created) = match existing {
+        Some(profile) => (profile, false),
+        None => {
+            let name = name.unwrap_or_else(|| {
+                tauri::Url::parse(&url)
+                    .ok()
+                    .and_then(|u| u.host_str().map(|h| h.to_string()))
+                    .unwrap_or_else(|| "Subscription".to_string())
+            });
+            (create_profile(name, Some(url))?, true)
+        }
+    };
+
+    if let Err(e) = update_profile_from_url(profile.id.clone()).await {
+        // Don't leave an empty profile behind when the first download fails
+        if created {
+            let _ = remove_profile(&profile.id);
+        }
+        return Err(e);
+    }
+
+    load_profiles_data()
+        .profiles
+        .into_iter()
+        .find(|p| p.id == profile.id)
+        .ok_or_else(|| "Profile not found".to_string())
+}
+
 #[tauri::command]
 pub fn parse_proxy_url(url: String) -> Result<serde_json::Value, String> {
     parse_proxy_url_value(&url)
@@ -1295,6 +1773,11 @@ pub fn save_config_obj(id: String, config: serde_json::Value) -> Result<(), Stri
         serde_json::from_value(config).map_err(|e| format!("Invalid config data: {}", e))?;
 
     let content = serde_yaml::to_string(&yaml_value).map_err(|e| e.to_string())?;
+    crate::core::snapshot_config(
+        std::path::Path::new(&profile.file_path),
+        &format!("profile-{}", id),
+        &content,
+    );
     fs::write(&profile.file_path, content).map_err(|e| e.to_string())?;
 
     profile.updated_at = get_current_time();
@@ -1315,3 +1798,148 @@ pub fn get_active_profile_path() -> Result<Option<String>, String> {
 
     Ok(None)
 }
+
+/// Export a profile as a subscription: `clash` returns the profile YAML, `base64-links`
+/// a base64-encoded newline-separated list of share links. Built-in DIRECT/REJECT
+/// entries are left out of both.
+#[tauri::command]
+pub fn export_subscription(profile_id: String, format: String) -> Result<String, String> {
+    let content = get_profile_content(profile_id)?;
+    let mut config: serde_yaml::Value =
+        serde_yaml::from_str(&content).map_err(|e| format!("Invalid YAML in profile: {}", e))?;
+
+    let proxies_key = serde_yaml::Value::String("proxies".to_string());
+    let proxies: Vec<serde_yaml::Value> = config
+        .get(&proxies_key)
+        .and_then(|v| v.as_sequence())
+        .map(|seq| {
+            seq.iter()
+                .filter(|p| !is_pseudo_proxy(p))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+
+    match format.as_str() {
+        "clash" => {
+            if let Some(config_obj) = config.as_mapping_mut() {
+                if config_obj.contains_key(&proxies_key) {
+                    config_obj.insert(proxies_key, serde_yaml::Value::Sequence(proxies));
+                }
+            }
+            serde_yaml::to_string(&config).map_err(|e| e.to_string())
+        }
+        "base64-links" => {
+            use base64::{engine::general_purpose, Engine as _};
+
+            let mut links = Vec::new();
+            for proxy in &proxies {
+                let proxy_json = serde_json::to_value(proxy).map_err(|e| e.to_string())?;
+                match proxy_to_url(&proxy_json) {
+                    Some(link) => links.push(link),
+                    None => tracing::info!(
+                        "Skipping proxy without share-link form: {}",
+                        proxy_field(&proxy_json, "name").unwrap_or_default()
+                    ),
+                }
+            }
+            if links.is_empty() {
+                return Err("Profile has no proxies that can be exported as links".to_string());
+            }
+            Ok(general_purpose::STANDARD.encode(links.join("\n")))
+        }
+        _ => Err(format!("Unsupported export format: {}", format)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(proxy: serde_json::Value) -> serde_json::Value {
+        let url = proxy_to_url(&proxy).expect("proxy should serialize");
+        parse_proxy_url_value(&url).expect("exported link should parse")
+    }
+
+    #[test]
+    fn ss_round_trips() {
+        let parsed = round_trip(serde_json::json!({
+            "name": "HK 01",
+            "type": "ss",
+            "server": "hk.example.com",
+            "port": 8388,
+            "cipher": "aes-256-gcm",
+            "password": "p@ss:word",
+        }));
+
+        assert_eq!(parsed["name"], "HK 01");
+        assert_eq!(parsed["server"], "hk.example.com");
+        assert_eq!(parsed["port"], 8388);
+        assert_eq!(parsed["cipher"], "aes-256-gcm");
+        assert_eq!(parsed["password"], "p@ss:word");
+    }
+
+    #[test]
+    fn ss_with_plugin_is_skipped() {
+        let proxy = serde_json::json!({
+            "name": "obfs",
+            "type": "ss",
+            "server": "1.2.3.4",
+            "port": 443,
+            "cipher": "aes-128-gcm",
+            "password": "secret",
+            "plugin": "obfs",
+            "plugin-opts": { "mode": "tls" },
+        });
+
+        assert!(proxy_to_url(&proxy).is_none());
+    }
+
+    #[test]
+    fn trojan_round_trips_with_tls_and_transport() {
+        let parsed = round_trip(serde_json::json!({
+            "name": "JP #2",
+            "type": "trojan",
+            "server": "jp.example.com",
+            "port": 443,
+            "password": "a/b?c#d@e",
+            "sni": "cdn.example.com",
+            "skip-cert-verify": true,
+            "network": "ws",
+            "ws-opts": { "path": "/ray", "headers": { "Host": "cdn.example.com" } },
+        }));
+
+        assert_eq!(parsed["name"], "JP #2");
+        assert_eq!(parsed["server"], "jp.example.com");
+        assert_eq!(parsed["port"], 443);
+        assert_eq!(parsed["password"], "a/b?c#d@e");
+        assert_eq!(parsed["sni"], "cdn.example.com");
+        assert_eq!(parsed["skip-cert-verify"], true);
+        assert_eq!(parsed["network"], "ws");
+        assert_eq!(parsed["path"], "/ray");
+        assert_eq!(parsed["host"], "cdn.example.com");
+    }
+
+    #[test]
+    fn socks5_round_trips_credentials() {
+        let parsed = round_trip(serde_json::json!({
+            "name": "local",
+            "type": "socks5",
+            "server": "::1",
+            "port": 1080,
+            "username": "user",
+            "password": "p:w@d",
+        }));
+
+        assert_eq!(parsed["server"], "::1");
+        assert_eq!(parsed["port"], 1080);
+        assert_eq!(parsed["username"], "user");
+        assert_eq!(parsed["password"], "p:w@d");
+    }
+
+    #[test]
+    fn pseudo_proxies_have_no_link() {
+        let direct = serde_json::json!({ "name": "DIRECT", "type": "direct" });
+        assert!(proxy_to_url(&direct).is_none());
+    }
+}
@@ -35,12 +35,6 @@ pub async fn get_version() -> Result<String, String> {
     }
 }
 
-/// Check if service version matches
-pub async fn check_version_match() -> Result<bool, String> {
-    let version = get_version().await?;
-    Ok(version == VERSION)
-}
-
 /// Start core via service
 #[allow(dead_code)]
 pub async fn start_core(
@@ -158,6 +152,22 @@ pub async fn clear_logs() -> Result<(), String> {
     }
 }
 
+/// Arm or disarm the kill-switch, returns whether traffic is currently blocked
+pub async fn set_kill_switch(enabled: bool, watch_port: Option<u16>) -> Result<bool, String> {
+    let response = aqiu_service_ipc::set_kill_switch(enabled, watch_port)
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    if !response.is_success() {
+        return Err(response.message);
+    }
+    
+    match response.data {
+        Some(ResponseData::Bool(blocking)) => Ok(blocking),
+        _ => Err("Invalid response data".to_string()),
+    }
+}
+
 /// Ping service
 #[allow(dead_code)]
 pub async fn ping() -> Result<(), String> {
@@ -177,20 +187,101 @@ pub async fn ping() -> Result<(), String> {
 pub enum ServiceStatus {
     /// Service is ready and version matches
     Ready,
-    /// Service needs reinstall (version mismatch)
-    NeedsReinstall,
+    /// Service needs reinstall, with the reason (version mismatch, broken plist,
+    /// missing binary or insecure socket)
+    NeedsReinstall(String),
     /// Service is not installed
     NotInstalled,
     /// Service is unavailable
     Unavailable(String),
 }
 
+/// LaunchDaemon plist (shared label for the service and the Service Mode core)
+#[cfg(target_os = "macos")]
+pub(crate) const SERVICE_PLIST_PATH: &str = "/Library/LaunchDaemons/com.aqiu.service.plist";
+
+/// Extract the program path (first `ProgramArguments` entry) from a plist
+#[cfg(target_os = "macos")]
+fn read_plist_program_path(content: &str) -> Option<String> {
+    let args = content.split("<key>ProgramArguments</key>").nth(1)?;
+    let start = args.find("<string>")? + "<string>".len();
+    let end = args[start..].find("</string>")? + start;
+    Some(args[start..end].trim().to_string())
+}
+
+/// Verify the installed plist points at an existing, executable binary.
+/// A failed install or partial upgrade can leave the plist behind without the binary,
+/// so launchd keeps failing to start it. Returns the reason a reinstall is needed.
+#[cfg(target_os = "macos")]
+pub fn check_plist_program() -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let Ok(content) = std::fs::read_to_string(SERVICE_PLIST_PATH) else {
+        // No plist: nothing installed, nothing to verify
+        return Ok(());
+    };
+
+    let program = read_plist_program_path(&content)
+        .ok_or_else(|| format!("{} has no ProgramArguments", SERVICE_PLIST_PATH))?;
+
+    let metadata = std::fs::metadata(&program)
+        .map_err(|_| format!("Service binary is missing: {}", program))?;
+    if !metadata.is_file() || metadata.permissions().mode() & 0o111 == 0 {
+        return Err(format!("Service binary is not executable: {}", program));
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn check_plist_program() -> Result<(), String> {
+    Ok(())
+}
+
+/// The daemon creates its socket as root with mode 0660; anything else means it was
+/// created by something else (or an old daemon) and shouldn't be trusted
+#[cfg(unix)]
+fn check_socket_security() -> Result<(), String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = std::fs::metadata(IPC_PATH)
+        .map_err(|e| format!("Cannot inspect service socket {}: {}", IPC_PATH, e))?;
+    if metadata.uid() != 0 {
+        return Err(format!(
+            "Service socket {} is not owned by root (uid {})",
+            IPC_PATH,
+            metadata.uid()
+        ));
+    }
+    if metadata.mode() & 0o002 != 0 {
+        return Err(format!("Service socket {} is world-writable", IPC_PATH));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_socket_security() -> Result<(), String> {
+    Ok(())
+}
+
 /// Check comprehensive service status
 pub async fn check_service_status() -> ServiceStatus {
+    // Installed but pointing at a missing binary: the daemon can't be running,
+    // so check this before treating a missing socket as "not installed"
+    if let Err(reason) = check_plist_program() {
+        tracing::info!("Service needs reinstall: {}", reason);
+        return ServiceStatus::NeedsReinstall(reason);
+    }
+    
     // First check if socket exists
     if !is_service_socket_exists() {
         return ServiceStatus::NotInstalled;
     }
+
+    if let Err(reason) = check_socket_security() {
+        tracing::warn!("Service needs reinstall: {}", reason);
+        return ServiceStatus::NeedsReinstall(reason);
+    }
     
     // Try to ping
     if !is_service_available().await {
@@ -198,13 +289,29 @@ pub async fn check_service_status() -> ServiceStatus {
     }
     
     // Check version
-    match check_version_match().await {
-        Ok(true) => ServiceStatus::Ready,
-        Ok(false) => ServiceStatus::NeedsReinstall,
+    match get_version().await {
+        Ok(version) if version == VERSION => ServiceStatus::Ready,
+        Ok(version) => ServiceStatus::NeedsReinstall(format!(
+            "Service v{} does not match app v{}",
+            version, VERSION
+        )),
         Err(e) => ServiceStatus::Unavailable(e),
     }
 }
 
+/// Confirm the daemon speaks our protocol version before issuing privileged commands.
+/// A missing or unreachable daemon is fine (callers fall back or install it); a
+/// mismatched one is not, since it may mishandle requests it doesn't know about.
+pub async fn ensure_compatible_service() -> Result<(), String> {
+    match check_service_status().await {
+        ServiceStatus::NeedsReinstall(reason) => Err(format!(
+            "NeedsReinstall: {}, please reinstall the service",
+            reason
+        )),
+        ServiceStatus::Ready | ServiceStatus::NotInstalled | ServiceStatus::Unavailable(_) => Ok(()),
+    }
+}
+
 /// Install service using the install script
 /// NOTE: This is the ONLY place that requires admin password (one-time setup)
 #[cfg(target_os = "macos")]
@@ -232,7 +339,7 @@ pub async fn install_service(app: &tauri::AppHandle) -> Result<(), String> {
         }
     }
     
-    println!("Installing service using script: {:?}", install_script);
+    tracing::info!("Installing service using script: {:?}", install_script);
     
     // Use osascript with AppleScript to show native macOS authorization dialog
     // Use /bin/bash to execute script since it may not have +x permission
@@ -251,7 +358,11 @@ pub async fn install_service(app: &tauri::AppHandle) -> Result<(), String> {
     if output.status.success() {
         // Wait for service to start
         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-        println!("Service installed successfully");
+
+        // A stale bundled daemon would still fail the handshake after installing
+        check_plist_program()?;
+        ensure_compatible_service().await?;
+        tracing::info!("Service installed successfully");
         Ok(())
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -288,7 +399,13 @@ pub async fn uninstall_service(app: &tauri::AppHandle) -> Result<(), String> {
         }
     }
     
-    println!("Uninstalling service using script: {:?}", uninstall_script);
+    // Uninstalling is also the way out of a mismatched daemon, so don't block on the
+    // handshake here; just record what is being removed.
+    if let Err(e) = ensure_compatible_service().await {
+        tracing::warn!("Uninstalling incompatible service: {}", e);
+    }
+
+    tracing::info!("Uninstalling service using script: {:?}", uninstall_script);
     
     // Use osascript with AppleScript to show native macOS authorization dialog
     // Use /bin/bash to execute script since it may not have +x permission
@@ -305,7 +422,7 @@ pub async fn uninstall_service(app: &tauri::AppHandle) -> Result<(), String> {
         .map_err(|e| format!("Failed to run uninstall script: {}", e))?;
     
     if output.status.success() {
-        println!("Service uninstalled successfully");
+        tracing::info!("Service uninstalled successfully");
         Ok(())
     } else {
         let stderr = String::from_utf8_lossy(&output.stderr);
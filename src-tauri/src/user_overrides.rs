@@ -23,7 +23,44 @@ pub struct UserConfigOverrides {
     )]
     pub external_controller: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(rename = "log-level", skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tun: Option<TunOverride>,
+    /// Extra `dns.fake-ip-filter` entries merged after the profile and proxy-server entries
+    #[serde(rename = "fake-ip-filter", skip_serializing_if = "Option::is_none")]
+    pub fake_ip_filter: Option<Vec<String>>,
+    /// Replaces `dns.proxy-server-nameserver` (set by the DNS fix strategies)
+    #[serde(
+        rename = "proxy-server-nameserver",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub proxy_server_nameserver: Option<Vec<String>>,
+    /// How often the background task refreshes geodata (days, 0 disables)
+    #[serde(
+        rename = "geodata-update-interval-days",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub geodata_update_interval_days: Option<u32>,
+    /// Block outbound traffic through the service while the core is unexpectedly down
+    #[serde(rename = "kill-switch", skip_serializing_if = "Option::is_none")]
+    pub kill_switch: Option<bool>,
+    /// Put the base template's rules ahead of the profile's, so the template wins
+    #[serde(
+        rename = "base-template-rules-first",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub base_template_rules_first: Option<bool>,
+    /// Defer scheduled updates while the OS reports a metered connection
+    #[serde(
+        rename = "pause-updates-on-metered",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pause_updates_on_metered: Option<bool>,
+    /// File name of the core binary under `cores/` to run instead of the default one
+    #[serde(rename = "core-binary", skip_serializing_if = "Option::is_none")]
+    pub core_binary: Option<String>,
     /// Persisted core mode preference (macOS only: "user" or "service")
     #[serde(rename = "core-mode", skip_serializing_if = "Option::is_none")]
     pub core_mode: Option<String>,
@@ -50,6 +87,12 @@ pub struct TunOverride {
     pub auto_detect_interface: Option<bool>,
     #[serde(rename = "dns-hijack", skip_serializing_if = "Option::is_none")]
     pub dns_hijack: Option<Vec<String>>,
+    /// Destination CIDRs kept off the TUN route when `auto-route` is on
+    #[serde(
+        rename = "route-exclude-address",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub route_exclude_address: Option<Vec<String>>,
 }
 
 impl TunOverride {
@@ -62,6 +105,29 @@ impl TunOverride {
             || self.auto_route.is_some()
             || self.auto_detect_interface.is_some()
             || self.dns_hijack.is_some()
+            || self.route_exclude_address.is_some()
+    }
+}
+
+impl UserConfigOverrides {
+    /// Whether any field that is written into the Mihomo config is set
+    pub fn has_effective_fields(&self) -> bool {
+        self.port.is_some()
+            || self.socks_port.is_some()
+            || self.mixed_port.is_some()
+            || self.redir_port.is_some()
+            || self.tproxy_port.is_some()
+            || self.allow_lan.is_some()
+            || self.external_controller.is_some()
+            || self.mode.is_some()
+            || self.log_level.is_some()
+            || self.fake_ip_filter.is_some()
+            || self.proxy_server_nameserver.is_some()
+            || self
+                .tun
+                .as_ref()
+                .map(|tun| tun.has_effective_fields())
+                .unwrap_or(false)
     }
 }
 
@@ -96,6 +162,84 @@ pub fn save_overrides(overrides: &UserConfigOverrides) -> Result<(), String> {
     Ok(())
 }
 
+fn is_ip_literal(s: &str) -> bool {
+    s.parse::<std::net::IpAddr>().is_ok()
+}
+
+fn is_valid_cidr(s: &str) -> bool {
+    let Some((addr, prefix)) = s.split_once('/') else {
+        return false;
+    };
+    let Ok(prefix) = prefix.parse::<u8>() else {
+        return false;
+    };
+    match addr.parse::<std::net::IpAddr>() {
+        Ok(std::net::IpAddr::V4(_)) => prefix <= 32,
+        Ok(std::net::IpAddr::V6(_)) => prefix <= 128,
+        Err(_) => false,
+    }
+}
+
+/// Validate and dedupe route exclusion CIDRs (e.g. `10.0.0.0/8`, `fd00::/8`)
+fn normalize_route_exclusions(entries: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let trimmed = entry.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if !is_valid_cidr(trimmed) {
+            return Err(format!("Invalid CIDR in tun.route-exclude-address: {:?}", trimmed));
+        }
+        if !normalized.iter().any(|e| e == trimmed) {
+            normalized.push(trimmed.to_string());
+        }
+    }
+    Ok(normalized)
+}
+
+fn looks_like_domain(s: &str) -> bool {
+    // Heuristic: contains at least one dot and at least one letter.
+    s.contains('.') && s.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+/// Collect proxy `server` domains (plus `+.` wildcard forms) that must never resolve to Fake-IPs
+pub fn collect_proxy_server_domains(root: &serde_yaml::Mapping) -> Vec<String> {
+    let mut out = Vec::new();
+    let proxies_key = serde_yaml::Value::String("proxies".to_string());
+    let Some(serde_yaml::Value::Sequence(items)) = root.get(&proxies_key) else {
+        return out;
+    };
+
+    for item in items {
+        let Some(m) = item.as_mapping() else {
+            continue;
+        };
+        let server_key = serde_yaml::Value::String("server".to_string());
+        let Some(server) = m
+            .get(&server_key)
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+        else {
+            continue;
+        };
+
+        if is_ip_literal(server) {
+            continue;
+        }
+        if looks_like_domain(server) {
+            out.push(server.to_string());
+            // Also add subdomain wildcard form to cover common patterns.
+            out.push(format!("+.{}", server));
+        }
+    }
+
+    out.sort();
+    out.dedup();
+    out
+}
+
 /// Apply user overrides to a YAML config
 pub fn apply_overrides_to_yaml(
     yaml: &mut serde_yaml::Value,
@@ -156,6 +300,20 @@ pub fn apply_overrides_to_yaml(
         );
     }
 
+    if let Some(ref mode) = overrides.mode {
+        root.insert(
+            serde_yaml::Value::String("mode".to_string()),
+            serde_yaml::Value::String(mode.clone()),
+        );
+    }
+
+    if let Some(ref log_level) = overrides.log_level {
+        root.insert(
+            serde_yaml::Value::String("log-level".to_string()),
+            serde_yaml::Value::String(log_level.clone()),
+        );
+    }
+
     // Apply TUN overrides
     if let Some(ref tun_override) = overrides.tun {
         if tun_override.has_effective_fields() {
@@ -167,13 +325,13 @@ pub fn apply_overrides_to_yaml(
 
             if let serde_yaml::Value::Mapping(ref mut map) = tun_value {
                 if let Some(enable) = tun_override.enable {
-                    println!("apply_overrides_to_yaml: Setting TUN enable to {}", enable);
+                    tracing::debug!("apply_overrides_to_yaml: Setting TUN enable to {}", enable);
                     map.insert(
                         serde_yaml::Value::String("enable".to_string()),
                         serde_yaml::Value::Bool(enable),
                     );
                 } else {
-                    println!("apply_overrides_to_yaml: WARNING - TUN override enable is None!");
+                    tracing::warn!("apply_overrides_to_yaml: WARNING - TUN override enable is None!");
                 }
                 if let Some(ref stack) = tun_override.stack {
                     map.insert(
@@ -221,6 +379,16 @@ pub fn apply_overrides_to_yaml(
                         serde_yaml::Value::Sequence(seq),
                     );
                 }
+                if let Some(ref exclusions) = tun_override.route_exclude_address {
+                    let seq = exclusions
+                        .iter()
+                        .map(|cidr| serde_yaml::Value::String(cidr.clone()))
+                        .collect();
+                    map.insert(
+                        serde_yaml::Value::String("route-exclude-address".to_string()),
+                        serde_yaml::Value::Sequence(seq),
+                    );
+                }
             }
 
             root.insert(tun_key, tun_value);
@@ -251,7 +419,7 @@ pub fn apply_overrides_to_yaml(
                 hijack_seq.push(serde_yaml::Value::String("any:53".to_string()));
                 hijack_seq.push(serde_yaml::Value::String("tcp://any:53".to_string()));
                 tun_map.insert(hijack_key, serde_yaml::Value::Sequence(hijack_seq));
-                println!("TUN mode: Added default dns-hijack configuration");
+                tracing::info!("TUN mode: Added default dns-hijack configuration");
             }
         }
 
@@ -317,7 +485,7 @@ pub fn apply_overrides_to_yaml(
                     // Service Mode runs as root on macOS, so :53 is OK.
                     // Keep it loopback-only to reduce surface area.
                     map.insert(k, serde_yaml::Value::String("127.0.0.1:53".to_string()));
-                    println!("DNS: Added dns.listen=127.0.0.1:53 for TUN mode");
+                    tracing::info!("DNS: Added dns.listen=127.0.0.1:53 for TUN mode");
                 }
             }
 
@@ -447,52 +615,8 @@ pub fn apply_overrides_to_yaml(
             // to a Fake-IP (198.18.0.0/16). Then the dial tries to connect to a non-existent host.
             //
             // To avoid that, we automatically add all `server` domains from `proxies` into
-            // dns.fake-ip-filter, so they always resolve to REAL IPs.
-            fn is_ip_literal(s: &str) -> bool {
-                s.parse::<std::net::IpAddr>().is_ok()
-            }
-
-            fn looks_like_domain(s: &str) -> bool {
-                // Heuristic: contains at least one dot and at least one letter.
-                s.contains('.') && s.chars().any(|c| c.is_ascii_alphabetic())
-            }
-
-            fn collect_proxy_server_domains(root: &serde_yaml::Mapping) -> Vec<String> {
-                let mut out = Vec::new();
-                let proxies_key = serde_yaml::Value::String("proxies".to_string());
-                let Some(serde_yaml::Value::Sequence(items)) = root.get(&proxies_key) else {
-                    return out;
-                };
-
-                for item in items {
-                    let Some(m) = item.as_mapping() else {
-                        continue;
-                    };
-                    let server_key = serde_yaml::Value::String("server".to_string());
-                    let Some(server) = m
-                        .get(&server_key)
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.trim())
-                        .filter(|s| !s.is_empty())
-                    else {
-                        continue;
-                    };
-
-                    if is_ip_literal(server) {
-                        continue;
-                    }
-                    if looks_like_domain(server) {
-                        out.push(server.to_string());
-                        // Also add subdomain wildcard form to cover common patterns.
-                        out.push(format!("+.{}", server));
-                    }
-                }
-
-                out.sort();
-                out.dedup();
-                out
-            }
-
+            // dns.fake-ip-filter, so they always resolve to REAL IPs
+            // (see `collect_proxy_server_domains`).
             // Add proxy server domains to fake-ip-filter to prevent them from being resolved to Fake-IPs
             // This is CRITICAL: if proxy server domains get fake-ip, the proxy connection will fail!
             let proxy_domains = collect_proxy_server_domains(root);
@@ -517,7 +641,7 @@ pub fn apply_overrides_to_yaml(
                 }
 
                 if added > 0 {
-                    println!(
+                    tracing::info!(
                         "DNS: Added {} proxy server entries to fake-ip-filter to avoid Fake-IP dialing",
                         added
                     );
@@ -540,6 +664,47 @@ pub fn apply_overrides_to_yaml(
         }
     }
 
+    // Append user fake-ip-filter entries (e.g. local services that must resolve to real IPs).
+    // Only meaningful when the config has a DNS section; we don't enable DNS just for this.
+    if let Some(ref entries) = overrides.fake_ip_filter {
+        let dns_key = serde_yaml::Value::String("dns".to_string());
+        if let Some(serde_yaml::Value::Mapping(ref mut dns_map)) = root.get_mut(&dns_key) {
+            let filter_key = serde_yaml::Value::String("fake-ip-filter".to_string());
+            let mut seq = match dns_map.get(&filter_key).cloned() {
+                Some(serde_yaml::Value::Sequence(s)) => s,
+                _ => serde_yaml::Sequence::new(),
+            };
+
+            let mut existing: std::collections::HashSet<String> = seq
+                .iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect();
+
+            for entry in entries {
+                if existing.insert(entry.clone()) {
+                    seq.push(serde_yaml::Value::String(entry.clone()));
+                }
+            }
+
+            dns_map.insert(filter_key, serde_yaml::Value::Sequence(seq));
+        }
+    }
+
+    // Replace the resolvers used for proxy server domains (overrides the TUN defaults above)
+    if let Some(ref nameservers) = overrides.proxy_server_nameserver {
+        let dns_key = serde_yaml::Value::String("dns".to_string());
+        if let Some(serde_yaml::Value::Mapping(ref mut dns_map)) = root.get_mut(&dns_key) {
+            let seq = nameservers
+                .iter()
+                .map(|ns| serde_yaml::Value::String(ns.clone()))
+                .collect();
+            dns_map.insert(
+                serde_yaml::Value::String("proxy-server-nameserver".to_string()),
+                serde_yaml::Value::Sequence(seq),
+            );
+        }
+    }
+
     Ok(())
 }
 
@@ -569,6 +734,49 @@ pub fn set_user_override(key: String, value: serde_json::Value) -> Result<(), St
         "external-controller" => {
             overrides.external_controller = value.as_str().map(|s| s.to_string());
         }
+        "mode" => {
+            if value.is_null() {
+                overrides.mode = None;
+            } else if let Some(val) = value.as_str() {
+                let normalized = val.to_lowercase();
+                if !matches!(normalized.as_str(), "rule" | "global" | "direct") {
+                    return Err("mode must be one of: rule, global, direct".to_string());
+                }
+                overrides.mode = Some(normalized);
+            } else {
+                return Err("mode expects a string".to_string());
+            }
+        }
+        "log-level" => {
+            if value.is_null() {
+                overrides.log_level = None;
+            } else if let Some(val) = value.as_str() {
+                let normalized = val.to_lowercase();
+                if !matches!(
+                    normalized.as_str(),
+                    "silent" | "error" | "warning" | "info" | "debug"
+                ) {
+                    return Err(
+                        "log-level must be one of: silent, error, warning, info, debug".to_string(),
+                    );
+                }
+                overrides.log_level = Some(normalized);
+            } else {
+                return Err("log-level expects a string".to_string());
+            }
+        }
+        "geodata-update-interval-days" => {
+            if value.is_null() {
+                overrides.geodata_update_interval_days = None;
+            } else if let Some(num) = value.as_u64() {
+                if num > 365 {
+                    return Err("geodata-update-interval-days must be <= 365".to_string());
+                }
+                overrides.geodata_update_interval_days = Some(num as u32);
+            } else {
+                return Err("geodata-update-interval-days expects a non-negative integer".to_string());
+            }
+        }
         key if key.starts_with("tun.") => {
             if overrides.tun.is_none() {
                 overrides.tun = Some(TunOverride::default());
@@ -657,6 +865,28 @@ pub fn set_user_override(key: String, value: serde_json::Value) -> Result<(), St
                         return Err("tun.dns-hijack expects an array of strings".to_string());
                     }
                 }
+                "route-exclude-address" => {
+                    if value.is_null() {
+                        tun.route_exclude_address = None;
+                    } else if let Some(entries) = value.as_array() {
+                        let mut list = Vec::with_capacity(entries.len());
+                        for entry in entries {
+                            if let Some(val) = entry.as_str() {
+                                list.push(val.to_string());
+                            } else {
+                                return Err(
+                                    "tun.route-exclude-address entries must be strings".to_string(),
+                                );
+                            }
+                        }
+                        let list = normalize_route_exclusions(list)?;
+                        tun.route_exclude_address = if list.is_empty() { None } else { Some(list) };
+                    } else {
+                        return Err(
+                            "tun.route-exclude-address expects an array of CIDR strings".to_string(),
+                        );
+                    }
+                }
                 _ => return Err(format!("Unknown TUN override key: {}", key)),
             }
         }
@@ -677,17 +907,69 @@ pub fn clear_user_overrides() -> Result<(), String> {
     save_overrides(&UserConfigOverrides::default())
 }
 
+/// Validate, dedupe and persist the user's fake-ip-filter entries.
+/// Returns the normalized list that was saved.
+pub fn persist_fake_ip_filter_entries(entries: Vec<String>) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let trimmed = entry.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.chars().any(|c| c.is_whitespace()) {
+            return Err(format!("Invalid fake-ip-filter entry: {:?}", trimmed));
+        }
+        if !normalized.iter().any(|e| e == trimmed) {
+            normalized.push(trimmed.to_string());
+        }
+    }
+
+    let mut overrides = load_overrides();
+    overrides.fake_ip_filter = if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized.clone())
+    };
+    save_overrides(&overrides)?;
+
+    Ok(normalized)
+}
+
+/// Persist the `proxy-server-nameserver` override (None restores the defaults)
+pub fn persist_proxy_server_nameserver(nameservers: Option<Vec<String>>) -> Result<(), String> {
+    let mut overrides = load_overrides();
+    overrides.proxy_server_nameserver = nameservers;
+    save_overrides(&overrides)
+}
+
+/// Validate, dedupe and persist the TUN route exclusion CIDRs.
+/// Returns the normalized list that was saved.
+pub fn persist_tun_route_exclusions(entries: Vec<String>) -> Result<Vec<String>, String> {
+    let normalized = normalize_route_exclusions(entries)?;
+
+    let mut overrides = load_overrides();
+    let tun = overrides.tun.get_or_insert_with(TunOverride::default);
+    tun.route_exclude_address = if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized.clone())
+    };
+    save_overrides(&overrides)?;
+
+    Ok(normalized)
+}
+
 /// Persist the latest TUN enable preference so UI stays consistent with runtime changes
 pub fn persist_tun_override(enable: bool) -> Result<(), String> {
-    println!("persist_tun_override: Setting TUN enable to {}", enable);
+    tracing::info!("persist_tun_override: Setting TUN enable to {}", enable);
     let mut overrides = load_overrides();
     if overrides.tun.is_none() {
-        println!("persist_tun_override: Creating new TUN override");
+        tracing::info!("persist_tun_override: Creating new TUN override");
         overrides.tun = Some(TunOverride::default());
     }
     if let Some(ref mut tun) = overrides.tun {
         tun.enable = Some(enable);
-        println!("persist_tun_override: TUN enable set to {:?}", tun.enable);
+        tracing::debug!("persist_tun_override: TUN enable set to {:?}", tun.enable);
 
         // When enabling TUN, ensure essential parameters are set for it to work
         if enable {
@@ -707,15 +989,15 @@ pub fn persist_tun_override(enable: bool) -> Result<(), String> {
             // dns-hijack: required for DNS resolution through TUN
             if tun.dns_hijack.is_none() {
                 tun.dns_hijack = Some(vec!["any:53".to_string(), "tcp://any:53".to_string()]);
-                println!("TUN mode: Setting default dns-hijack: any:53, tcp://any:53");
+                tracing::info!("TUN mode: Setting default dns-hijack: any:53, tcp://any:53");
             }
         }
     }
     let result = save_overrides(&overrides);
     if result.is_ok() {
-        println!("persist_tun_override: Successfully saved overrides to disk");
+        tracing::info!("persist_tun_override: Successfully saved overrides to disk");
     } else {
-        println!("persist_tun_override: ERROR saving overrides: {:?}", result);
+        tracing::warn!("persist_tun_override: ERROR saving overrides: {:?}", result);
     }
     result
 }
@@ -727,6 +1009,45 @@ pub fn persist_core_mode(mode: &str) -> Result<(), String> {
     save_overrides(&overrides)
 }
 
+/// Keep an existing `mode` override in step with a live mode switch, so the next restart
+/// doesn't revert it. Without an override the profile's own mode keeps applying.
+pub fn persist_mode_if_overridden(mode: &str) -> Result<(), String> {
+    let mut overrides = load_overrides();
+    if overrides.mode.is_none() {
+        return Ok(());
+    }
+    overrides.mode = Some(mode.to_string());
+    save_overrides(&overrides)
+}
+
+/// Persist the kill-switch preference
+pub fn persist_kill_switch(enable: bool) -> Result<(), String> {
+    let mut overrides = load_overrides();
+    overrides.kill_switch = Some(enable);
+    save_overrides(&overrides)
+}
+
+/// Persist whether base template rules take precedence over the profile's
+pub fn persist_base_template_rules_first(rules_first: Option<bool>) -> Result<(), String> {
+    let mut overrides = load_overrides();
+    overrides.base_template_rules_first = rules_first;
+    save_overrides(&overrides)
+}
+
+/// Persist whether scheduled updates wait for an unmetered connection
+pub fn persist_pause_updates_on_metered(enable: bool) -> Result<(), String> {
+    let mut overrides = load_overrides();
+    overrides.pause_updates_on_metered = Some(enable);
+    save_overrides(&overrides)
+}
+
+/// Persist the selected core binary (None goes back to the default binary)
+pub fn persist_core_binary(name: Option<String>) -> Result<(), String> {
+    let mut overrides = load_overrides();
+    overrides.core_binary = name;
+    save_overrides(&overrides)
+}
+
 /// Get persisted core mode preference
 pub fn get_persisted_core_mode() -> Option<String> {
     load_overrides().core_mode
@@ -72,3 +72,18 @@ export async function parseProxyUrl(url: string): Promise<any> {
 export async function getActiveProfilePath(): Promise<string | null> {
     return await invoke<string | null>('get_active_profile_path');
 }
+
+// ========== Deep Links ==========
+
+export interface DeepLinkImportRequest {
+    url: string;
+    name: string | null;
+}
+
+export async function takePendingDeepLinks(): Promise<DeepLinkImportRequest[]> {
+    return await invoke<DeepLinkImportRequest[]>('take_pending_deep_links');
+}
+
+export async function confirmDeepLinkImport(url: string, name: string | null): Promise<Profile> {
+    return await invoke<Profile>('confirm_deep_link_import', { url, name });
+}
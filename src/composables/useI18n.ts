@@ -350,6 +350,10 @@ const messages: Record<Locale, MessageValue> = {
             importFailed: 'Failed to parse proxy URL: {error}',
             proxyAdded: 'Proxy added successfully!',
             configSavedPath: 'Config saved to: {path}\nPlease restart core to apply.',
+            deepLinkImportTitle: 'Import Subscription',
+            deepLinkImportConfirm: 'A link asks to import the subscription "{name}" from:\n{url}\nImport it?',
+            deepLinkImported: 'Subscription "{name}" imported',
+            deepLinkFailed: 'Failed to import subscription: {error}',
         },
     },
     zh: {
@@ -696,6 +700,10 @@ const messages: Record<Locale, MessageValue> = {
             importFailed: '解析代理链接失败：{error}',
             proxyAdded: '节点已添加！',
             configSavedPath: '配置已保存到：{path}\n请重启内核生效。',
+            deepLinkImportTitle: '导入订阅',
+            deepLinkImportConfirm: '链接请求从以下地址导入订阅「{name}」：\n{url}\n是否导入？',
+            deepLinkImported: '订阅「{name}」已导入',
+            deepLinkFailed: '导入订阅失败：{error}',
         },
     },
 };